@@ -0,0 +1,392 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+/// Location of a repository inside its host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoPath {
+    pub owner: String,
+    pub name: String,
+    pub subdir: Option<String>,
+}
+
+/// Name and domain of a host, the name doubling as its shorthand prefix as in
+/// `gitlab:owner/repo`.
+#[derive(Debug, Clone)]
+pub struct Site {
+    pub name: String,
+    pub domain: String,
+    /// Base of the web urls, `https://<domain>` unless configured otherwise.
+    pub url: String,
+}
+
+impl Site {
+    pub fn new(name: &str, domain: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            domain: domain.to_owned(),
+            url: format!("https://{domain}"),
+        }
+    }
+}
+
+fn parse_repo_path(path: &str) -> Option<RepoPath> {
+    let re = Regex::new(
+        r"^(?<owner>~?[\w\-.]+)/(?<name>[\w\-.]+?)(?:\.git)?(?<subdir>(?:/[\w\-.]+)*)/?$",
+    )
+    .expect("valid repository path regex");
+    let captures = re.captures(path)?;
+
+    let repo = RepoPath {
+        owner: String::from(&captures["owner"]),
+        name: String::from(&captures["name"]),
+        subdir: captures
+            .name("subdir")
+            .map(|subdir| subdir.as_str().trim_start_matches('/'))
+            .filter(|subdir| !subdir.is_empty())
+            .map(String::from),
+    };
+    // `group/sub/repo.git` names a nested repository, not the `repo.git`
+    // directory of `group/sub`
+    if ends_a_repo(repo.subdir.iter().flat_map(|subdir| subdir.split('/'))) {
+        return None;
+    }
+    Some(repo)
+}
+
+fn ends_a_repo<'a>(mut segments: impl Iterator<Item = &'a str>) -> bool {
+    segments.any(|segment| segment.ends_with(".git"))
+}
+
+/// A git hosting provider, turning a [`RepoPath`] into the urls degit uses.
+pub trait Host: fmt::Debug + Send + Sync {
+    fn site(&self) -> &Site;
+
+    fn name(&self) -> &str {
+        &self.site().name
+    }
+
+    fn matches_domain(&self, domain: &str) -> bool {
+        self.site().domain == domain
+    }
+
+    /// Parses what follows the domain in a url, `owner/repo[.git][/sub/dir]` by default.
+    fn parse_path(&self, path: &str) -> Option<RepoPath> {
+        parse_repo_path(path)
+    }
+
+    fn https_url(&self, repo: &RepoPath) -> String {
+        format!("{}/{}/{}", self.site().url, repo.owner, repo.name)
+    }
+
+    fn ssh_url(&self, repo: &RepoPath) -> String {
+        format!("git@{}:{}/{}", self.site().domain, repo.owner, repo.name)
+    }
+
+    /// Url of a tarball of `git_ref`, when the host serves them.
+    fn archive_url(&self, repo: &RepoPath, git_ref: &str) -> Option<String>;
+}
+
+#[derive(Debug)]
+pub struct GitHub(pub Site);
+
+impl Host for GitHub {
+    fn site(&self) -> &Site {
+        &self.0
+    }
+
+    fn archive_url(&self, repo: &RepoPath, git_ref: &str) -> Option<String> {
+        Some(format!("{}/archive/{git_ref}.tar.gz", self.https_url(repo)))
+    }
+}
+
+#[derive(Debug)]
+pub struct GitLab(pub Site);
+
+impl Host for GitLab {
+    fn site(&self) -> &Site {
+        &self.0
+    }
+
+    /// Repositories can be nested in subgroups, as in `group/sub/repo.git`,
+    /// which is only unambiguous when `.git` ends the repository path.
+    fn parse_path(&self, path: &str) -> Option<RepoPath> {
+        let re = Regex::new(
+            r"^(?<owner>[\w\-.]+(?:/[\w\-.]+)+)/(?<name>[\w\-.]+)\.git(?<subdir>(?:/[\w\-.]+)*)/?$",
+        )
+        .expect("valid gitlab path regex");
+        let Some(captures) = re.captures(path) else {
+            return parse_repo_path(path);
+        };
+
+        let owner = String::from(&captures["owner"]);
+        let subdir = captures
+            .name("subdir")
+            .map(|subdir| subdir.as_str().trim_start_matches('/'))
+            .filter(|subdir| !subdir.is_empty())
+            .map(String::from);
+        let segments = owner
+            .split('/')
+            .chain(subdir.iter().flat_map(|subdir| subdir.split('/')));
+        if ends_a_repo(segments) {
+            return None;
+        }
+        Some(RepoPath {
+            owner,
+            name: String::from(&captures["name"]),
+            subdir,
+        })
+    }
+
+    fn archive_url(&self, repo: &RepoPath, git_ref: &str) -> Option<String> {
+        Some(format!(
+            "{}/-/archive/{git_ref}/{}-{git_ref}.tar.gz",
+            self.https_url(repo),
+            repo.name
+        ))
+    }
+}
+
+/// Gitea and its forks, such as Codeberg's Forgejo.
+#[derive(Debug)]
+pub struct Gitea(pub Site);
+
+impl Host for Gitea {
+    fn site(&self) -> &Site {
+        &self.0
+    }
+
+    fn archive_url(&self, repo: &RepoPath, git_ref: &str) -> Option<String> {
+        Some(format!("{}/archive/{git_ref}.tar.gz", self.https_url(repo)))
+    }
+}
+
+#[derive(Debug)]
+pub struct Bitbucket(pub Site);
+
+impl Host for Bitbucket {
+    fn site(&self) -> &Site {
+        &self.0
+    }
+
+    fn archive_url(&self, repo: &RepoPath, git_ref: &str) -> Option<String> {
+        Some(format!("{}/get/{git_ref}.tar.gz", self.https_url(repo)))
+    }
+}
+
+/// sourcehut, whose owners are written as `~user`.
+#[derive(Debug)]
+pub struct Sourcehut(pub Site);
+
+impl Host for Sourcehut {
+    fn site(&self) -> &Site {
+        &self.0
+    }
+
+    fn archive_url(&self, repo: &RepoPath, git_ref: &str) -> Option<String> {
+        Some(format!("{}/archive/{git_ref}.tar.gz", self.https_url(repo)))
+    }
+}
+
+/// Azure DevOps Repos, where the owner is `<organization>/<project>`.
+#[derive(Debug)]
+pub struct AzureDevOps(pub Site);
+
+impl AzureDevOps {
+    fn split_owner(repo: &RepoPath) -> (&str, &str) {
+        repo.owner.split_once('/').unwrap_or((&repo.owner, ""))
+    }
+}
+
+impl Host for AzureDevOps {
+    fn site(&self) -> &Site {
+        &self.0
+    }
+
+    fn matches_domain(&self, domain: &str) -> bool {
+        let domain = domain.strip_prefix("ssh.").unwrap_or(domain);
+        self.site().domain == domain
+    }
+
+    fn parse_path(&self, path: &str) -> Option<RepoPath> {
+        let re = Regex::new(
+            r"^(?:v\d/)?(?<organization>[\w\-]+)/(?<project>[\w\-.]+)(?:/_git)?/(?<name>[\w\-.]+)$",
+        )
+        .expect("valid azure path regex");
+        let captures = re.captures(path)?;
+
+        Some(RepoPath {
+            owner: format!("{}/{}", &captures["organization"], &captures["project"]),
+            name: String::from(&captures["name"]),
+            subdir: None,
+        })
+    }
+
+    fn https_url(&self, repo: &RepoPath) -> String {
+        let (organization, project) = Self::split_owner(repo);
+        format!(
+            "https://{organization}@{}/{organization}/{project}/_git/{}",
+            self.site().domain,
+            repo.name
+        )
+    }
+
+    fn ssh_url(&self, repo: &RepoPath) -> String {
+        let (organization, project) = Self::split_owner(repo);
+        format!(
+            "git@ssh.{}:v3/{organization}/{project}/{}",
+            self.site().domain,
+            repo.name
+        )
+    }
+
+    fn archive_url(&self, _repo: &RepoPath, _git_ref: &str) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HostKind {
+    Github,
+    Gitlab,
+    Gitea,
+    Bitbucket,
+    Sourcehut,
+}
+
+impl HostKind {
+    pub fn host(self, site: Site) -> Arc<dyn Host> {
+        match self {
+            HostKind::Github => Arc::new(GitHub(site)),
+            HostKind::Gitlab => Arc::new(GitLab(site)),
+            HostKind::Gitea => Arc::new(Gitea(site)),
+            HostKind::Bitbucket => Arc::new(Bitbucket(site)),
+            HostKind::Sourcehut => Arc::new(Sourcehut(site)),
+        }
+    }
+}
+
+/// Self-hosted instance declared by the user in `~/.config/degit/hosts.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ConfigHost {
+    kind: HostKind,
+    domain: String,
+    /// Shorthand prefix, defaults to the domain.
+    name: Option<String>,
+    /// Base of the web urls, defaults to `https://<domain>`.
+    url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HostsFile {
+    #[serde(default)]
+    hosts: Vec<ConfigHost>,
+}
+
+/// File user hosts are loaded from, `$XDG_CONFIG_HOME/degit/hosts.toml` or
+/// `~/.config/degit/hosts.toml`.
+pub fn hosts_file() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+    Some(config.join("degit").join("hosts.toml"))
+}
+
+/// Every host degit knows about, bare `owner/repo` shorthands belong to the first one.
+#[derive(Debug, Default)]
+pub struct Hosts {
+    hosts: Vec<Arc<dyn Host>>,
+}
+
+static HOSTS: OnceLock<Hosts> = OnceLock::new();
+
+/// Hosts used by degit, initialized with the built-in and user hosts on first use.
+pub fn hosts() -> &'static Hosts {
+    HOSTS.get_or_init(Hosts::load)
+}
+
+impl Hosts {
+    pub fn with_builtins() -> Self {
+        Self {
+            hosts: vec![
+                Arc::new(GitHub(Site::new("github", "github.com"))),
+                Arc::new(GitLab(Site::new("gitlab", "gitlab.com"))),
+                Arc::new(Bitbucket(Site::new("bitbucket", "bitbucket.org"))),
+                Arc::new(Gitea(Site::new("codeberg", "codeberg.org"))),
+                Arc::new(Sourcehut(Site::new("sr.ht", "git.sr.ht"))),
+                Arc::new(AzureDevOps(Site::new("azure", "dev.azure.com"))),
+            ],
+        }
+    }
+
+    /// Built-in hosts followed by the user ones, invalid files are reported and skipped.
+    pub fn load() -> Self {
+        let mut hosts = Self::with_builtins();
+        if let Some(path) = hosts_file().filter(|path| path.is_file()) {
+            if let Err(err) = hosts.register_file(&path) {
+                eprintln!("{err:#}");
+            }
+        }
+        hosts
+    }
+
+    pub fn register_file(&mut self, path: &Path) -> Result<()> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read hosts {}", path.display()))?;
+        let file: HostsFile = toml::from_str(&content)
+            .with_context(|| format!("Invalid hosts {}", path.display()))?;
+
+        for host in file.hosts {
+            let name = host.name.unwrap_or_else(|| host.domain.clone());
+            let mut site = Site::new(&name, &host.domain);
+            if let Some(url) = host.url {
+                site.url = url.trim_end_matches('/').to_owned();
+            }
+            self.register(host.kind.host(site));
+        }
+        Ok(())
+    }
+
+    pub fn register(&mut self, host: Arc<dyn Host>) {
+        self.hosts.push(host);
+    }
+
+    pub fn install(self) -> Result<()> {
+        if HOSTS.set(self).is_err() {
+            bail!("The hosts were already initialized");
+        }
+        Ok(())
+    }
+
+    pub fn default_host(&self) -> Option<Arc<dyn Host>> {
+        self.hosts.first().cloned()
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<Arc<dyn Host>> {
+        self.hosts.iter().find(|host| host.name() == name).cloned()
+    }
+
+    /// Host serving `domain`, self-hosted GitLab instances on a `gitlab.`
+    /// subdomain are recognized without being configured.
+    pub fn by_domain(&self, domain: &str) -> Option<Arc<dyn Host>> {
+        self.hosts
+            .iter()
+            .find(|host| host.matches_domain(domain))
+            .cloned()
+            .or_else(|| {
+                domain
+                    .starts_with("gitlab.")
+                    .then(|| Arc::new(GitLab(Site::new(domain, domain))) as Arc<dyn Host>)
+            })
+    }
+}
@@ -1,15 +1,34 @@
 use std::{
     fs,
-    path::Path,
-    process::{exit, Child, Command, Stdio},
+    io::{ErrorKind, Read},
+    path::{Component, Path},
+    process::{exit, Command, Stdio},
+    sync::Arc,
+    time::Duration,
 };
 
+pub mod hosts;
 pub mod options;
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use colored::Colorize;
-use options::Options;
+use flate2::read::GzDecoder;
+use hosts::{hosts, Host, RepoPath};
+use options::{Mode, Options};
 use regex::Regex;
+use tar::Archive;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest a download can go without receiving anything.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where self-hosted instances can be declared, for error messages.
+fn hosts_file_hint() -> String {
+    hosts::hosts_file().map_or_else(
+        || String::from("~/.config/degit/hosts.toml"),
+        |path| path.display().to_string(),
+    )
+}
 
 #[derive(Debug)]
 pub struct Repository {
@@ -18,59 +37,98 @@ pub struct Repository {
     pub https: String,
     pub ssh: String,
     pub host: String,
+    pub git_ref: Option<String>,
+    /// Directory of the repository to be extracted instead of the whole clone.
+    pub subdir: Option<String>,
+    pub provider: Arc<dyn Host>,
 }
 
 impl Repository {
+    /// Parses a full https or ssh url, or the degit shorthand: `owner/repo`, a
+    /// host prefix as in `gitlab:owner/repo` or a domain as in
+    /// `bitbucket.org/owner/repo`. A subdirectory can follow the repo name.
     pub fn from_url(url: &str) -> Result<Self> {
-        let is_azure = url.contains("azure");
+        // Like the original degit, a branch, tag or commit can follow a `#`
+        let (url, git_ref) = match url.rsplit_once('#') {
+            Some((url, git_ref)) if !git_ref.is_empty() => (url, Some(String::from(git_ref))),
+            _ => (url.trim_end_matches('#'), None),
+        };
 
-        let re = if is_azure {
-            Regex::new(
-                r"^(https://\w+@|git@ssh\.)(?<host>dev\.azure\.(?<dns>com)(:v\d)?)/(?<repo_owner>[\w\-]+)/(?<repo_project>[\w\-.]+)(/_git)?/(?<repo_name>[\w\-.]+)$",
-            )?
+        let full = Regex::new(
+            r"^(?:https?://(?:[\w\-.]+@)?|ssh://git@)(?<domain>[\w\-.]+)(?::\d+)?/(?<path>.+)$",
+        )?;
+        // Only the scp-like form separates the path with a `:`
+        let scp = Regex::new(r"^git@(?<domain>[\w\-.]+):(?<path>.+)$")?;
+        let prefixed = Regex::new(r"^(?<name>[\w\-.]+):(?<path>[^/].*)$")?;
+        let with_domain = Regex::new(r"^(?<domain>[\w\-]+(?:\.[\w\-]+)+)/(?<path>.+)$")?;
+
+        let (provider, path) = if let Some(captures) = full
+            .captures(url)
+            .or_else(|| scp.captures(url))
+            .or_else(|| with_domain.captures(url))
+        {
+            let domain = &captures["domain"];
+            let provider = hosts().by_domain(domain).with_context(|| {
+                format!(
+                    "Unsupported host {domain}, self-hosted ones can be added to {}",
+                    hosts_file_hint()
+                )
+            })?;
+            (
+                provider,
+                captures.name("path").map_or("", |path| path.as_str()),
+            )
+        } else if let Some(captures) = prefixed.captures(url) {
+            let name = &captures["name"];
+            let provider = hosts().by_name(name).with_context(|| {
+                format!(
+                    "Unknown host {name}, self-hosted ones can be added to {}",
+                    hosts_file_hint()
+                )
+            })?;
+            (
+                provider,
+                captures.name("path").map_or("", |path| path.as_str()),
+            )
         } else {
-            Regex::new(
-                r"^(https?://|git@)?(?<host>github|gitlab(\.[\w\-]*)?)\.(?<dns>com|org)(/|:)(?<repo_owner>[\w\-]+)/(?<repo_name>[\w\-.]+)\.git$",
-            )?
+            let provider = hosts()
+                .default_host()
+                .with_context(|| format!("No host is available for {url}"))?;
+            (provider, url)
         };
 
-        let Some(captures) = re.captures(url) else {
+        let Some(repo) = provider.parse_path(path) else {
             bail!("Invalid URL provided");
         };
 
-        let owner = String::from(&captures["repo_owner"]);
-        let name = String::from(&captures["repo_name"]);
-        let host = String::from(&captures["host"]);
-        let domain = format!("{}.{}", &host, &captures["dns"]);
-        let project = if is_azure {
-            String::from(&captures["repo_project"])
-        } else {
-            String::new()
-        };
-        let ssh = if is_azure {
-            format!("git@ssh.{}/{}/{}/{}", &host, &owner, &project, &name)
-        } else {
-            format!("git@{}:{}/{}", &domain, &owner, &name,)
-        };
-        let https = if is_azure {
-            format!(
-                "https://{}@{}/{}/{}/_git/{}",
-                &owner, &host, &owner, &project, &name,
-            )
-        } else {
-            format!("https://{}/{}/{}", &domain, &owner, &name)
-        };
         Ok(Self {
-            name,
-            owner,
-            https,
-            ssh,
-            host,
+            https: provider.https_url(&repo),
+            ssh: provider.ssh_url(&repo),
+            host: provider.name().to_owned(),
+            owner: repo.owner,
+            name: repo.name,
+            git_ref,
+            subdir: repo.subdir,
+            provider,
         })
     }
 
-    pub fn check_destination(&self, dest: &Option<String>) -> Result<String> {
-        let p = if let Some(d) = dest.as_deref() {
+    pub fn repo_path(&self) -> RepoPath {
+        RepoPath {
+            owner: self.owner.clone(),
+            name: self.name.clone(),
+            subdir: self.subdir.clone(),
+        }
+    }
+
+    /// Url of a tarball of `git_ref`, when the host serves them.
+    pub fn archive_url(&self, git_ref: &str) -> Option<String> {
+        self.provider.archive_url(&self.repo_path(), git_ref)
+    }
+
+    /// Path the repository is cloned to, `dest` ending with a `/` is a parent directory.
+    pub fn destination(&self, dest: &Option<String>) -> String {
+        if let Some(d) = dest.as_deref() {
             let mut p = String::from(d);
             if p.ends_with('/') {
                 p.push_str(&self.name);
@@ -78,7 +136,11 @@ impl Repository {
             p
         } else {
             format!("./{}", self.name)
-        };
+        }
+    }
+
+    pub fn check_destination(&self, dest: &Option<String>) -> Result<String> {
+        let p = self.destination(dest);
 
         let parsed_path = Path::new(&p);
 
@@ -96,73 +158,523 @@ impl Repository {
     }
 }
 
-pub fn degit(url: String, dest: Option<String>, ssh: bool) -> Result<Child> {
-    let repository = Repository::from_url(&url)?;
-    let clone_path = repository.check_destination(&dest)?;
+/// Everything needed to degit a repository, built without touching disk.
+#[derive(Debug)]
+pub struct Degit {
+    pub repository: Repository,
+    /// Branch, tag or commit to be cloned instead of the default branch.
+    pub git_ref: Option<String>,
+    pub subdir: Option<String>,
+    pub path: String,
+    url: String,
+}
 
-    let url = if ssh {
-        &repository.ssh
-    } else {
-        &repository.https
-    };
+impl Degit {
+    pub fn new(opts: &Options) -> Result<Self> {
+        let repository = Repository::from_url(&opts.url)?;
+        let path = repository.destination(&opts.dest);
+        let url = if opts.ssh {
+            repository.ssh.clone()
+        } else {
+            repository.https.clone()
+        };
+        let git_ref = opts.git_ref.clone().or_else(|| repository.git_ref.clone());
+        let subdir = opts.subdir.clone().or_else(|| repository.subdir.clone());
+        if let Some(subdir) = &subdir {
+            let escapes = Path::new(subdir)
+                .components()
+                .any(|component| !matches!(component, Component::Normal(_)));
+            if escapes {
+                bail!("Invalid subdirectory {subdir}, it must be relative to the repository root");
+            }
+        }
+
+        Ok(Self {
+            repository,
+            git_ref,
+            subdir,
+            path,
+            url,
+        })
+    }
+
+    /// Commands cloning the repository into [`Degit::path`], their output is
+    /// left for the caller to redirect. A ref is fetched alone with a shallow
+    /// fetch, since `git clone --branch` doesn't accept commits.
+    pub fn commands(&self) -> Vec<Command> {
+        let Some(git_ref) = &self.git_ref else {
+            let mut clone = Command::new("git");
+            clone
+                .args(["clone", "--depth", "1"])
+                .arg(&self.url)
+                .arg(&self.path);
+            return vec![clone];
+        };
+
+        let mut init = Command::new("git");
+        init.args(["init", "--quiet"]).arg(&self.path);
+        let mut fetch = Command::new("git");
+        fetch
+            .arg("-C")
+            .arg(&self.path)
+            .args(["fetch", "--quiet", "--depth", "1"])
+            .arg(&self.url)
+            .arg(git_ref);
+        let mut checkout = Command::new("git");
+        checkout
+            .arg("-C")
+            .arg(&self.path)
+            .args(["checkout", "--quiet", "FETCH_HEAD"]);
+
+        vec![init, fetch, checkout]
+    }
+
+    /// Downloads the tarball of the ref (the default branch when there's none)
+    /// and extracts it into [`Degit::path`], without git.
+    pub fn download(&self) -> Result<()> {
+        let git_ref = self.git_ref.as_deref().unwrap_or("HEAD");
+        let Some(url) = self.repository.archive_url(git_ref) else {
+            bail!("{} doesn't serve archives", self.repository.host);
+        };
+
+        // A stalled host should fall back to git rather than hang
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(CONNECT_TIMEOUT)
+            .timeout_read(READ_TIMEOUT)
+            .build();
+        let response = match agent.get(&url).call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(code, _)) => {
+                bail!("Failed to download {url}: status code {code}")
+            }
+            Err(err) => return Err(err).context("Failed to download archive"),
+        };
+        self.extract(response.into_reader())
+            .with_context(|| format!("Failed to extract {url}"))
+    }
+
+    /// Extracts a gzipped tarball, skipping the `<repo>-<ref>` directory hosts
+    /// wrap everything in and whatever is outside of the subdirectory.
+    pub fn extract(&self, archive: impl Read) -> Result<()> {
+        let mut archive = Archive::new(GzDecoder::new(archive));
+        let destination = Path::new(&self.path);
+        let subdir = self.subdir.as_deref().map(Path::new);
+        let mut extracted = false;
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_pax_global_extensions() {
+                continue;
+            }
+
+            let path = entry.path()?.into_owned();
+            let mut components = path.components();
+            components.next();
+            let mut relative = components.as_path();
+            if let Some(subdir) = subdir {
+                let Ok(inside) = relative.strip_prefix(subdir) else {
+                    continue;
+                };
+                relative = inside;
+            }
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+            if relative
+                .components()
+                .any(|component| !matches!(component, Component::Normal(_)))
+            {
+                bail!("Archive entry {} escapes the destination", path.display());
+            }
+            // `unpack` would resolve the link from the working directory
+            if entry_type.is_hard_link() {
+                bail!("Archive entry {} is a hard link", path.display());
+            }
+
+            if let Some(parent) = relative.parent() {
+                create_parents(destination, parent).with_context(|| {
+                    format!("Archive entry {} escapes the destination", path.display())
+                })?;
+            }
+            let target = destination.join(relative);
+            entry
+                .unpack(&target)
+                .with_context(|| format!("Failed to extract {}", target.display()))?;
+            extracted = true;
+        }
+
+        if !extracted {
+            match &self.subdir {
+                Some(subdir) => bail!(
+                    "{subdir} isn't a directory of {}/{}",
+                    self.repository.owner,
+                    self.repository.name
+                ),
+                None => bail!("The archive is empty"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Turns the clone into a plain directory, once every command succeeded.
+    /// With a subdirectory it takes the place of the clone, discarding the rest.
+    pub fn finish(&self) -> Result<()> {
+        let Some(subdir) = &self.subdir else {
+            return fs::remove_dir_all(format!("{}/.git", self.path))
+                .with_context(|| format!("Failed to remove the git history of {}", self.path));
+        };
 
-    Ok(Command::new("git")
-        .arg("clone")
-        .arg(url)
-        .arg(&clone_path)
-        .stdout(Stdio::null())
-        .stdin(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()?)
+        let clone = format!("{}.degit", self.path);
+        fs::rename(&self.path, &clone)
+            .with_context(|| format!("Failed to move the clone of {}", self.path))?;
+
+        let source = Path::new(&clone).join(subdir);
+        let result = if source.is_dir() {
+            fs::rename(&source, &self.path)
+                .with_context(|| format!("Failed to move {subdir} to {}", self.path))
+        } else {
+            Err(anyhow!(
+                "{subdir} isn't a directory of {}/{}",
+                self.repository.owner,
+                self.repository.name
+            ))
+        };
+
+        fs::remove_dir_all(&clone).with_context(|| format!("Failed to remove {clone}"))?;
+        result
+    }
+}
+
+/// Creates the directories of `relative` inside `destination` one at a time,
+/// refusing to go through anything that isn't a plain directory, like a symlink
+/// planted by an earlier entry of the archive.
+fn create_parents(destination: &Path, relative: &Path) -> Result<()> {
+    fs::create_dir_all(destination)?;
+    let mut path = destination.to_path_buf();
+    for component in relative.components() {
+        path.push(component);
+        match fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.is_dir() => {}
+            Ok(_) => bail!("{} isn't a directory", path.display()),
+            Err(err) if err.kind() == ErrorKind::NotFound => fs::create_dir(&path)?,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(())
 }
 
 pub fn run(opt: Options) -> Result<()> {
-    let repository = Repository::from_url(&opt.url)?;
-    let clone_path = repository.check_destination(&opt.dest)?;
+    let degit = Degit::new(&opt)?;
+    let repository = &degit.repository;
+    repository.check_destination(&opt.dest)?;
+    let suffix = degit
+        .subdir
+        .iter()
+        .map(|subdir| format!("/{subdir}"))
+        .chain(degit.git_ref.iter().map(|git_ref| format!("#{git_ref}")))
+        .collect::<String>();
 
-    let url = if opt.ssh {
-        &repository.ssh
-    } else {
-        &repository.https
-    };
+    if matches!(opt.mode, Mode::Tar) {
+        println!(
+            "Downloading {}{} to {} from {}",
+            format!("{}/{}", &repository.owner, &repository.name).cyan(),
+            suffix.cyan(),
+            &degit.path.green().bold(),
+            &repository.host.white().bold()
+        );
 
-    let git_process = Command::new("git")
-        .arg("clone")
-        .arg(url)
-        .arg(&clone_path)
-        .stdout(Stdio::null())
-        .stdin(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .with_context(|| "Failed to spawn git process")?;
+        match degit.download() {
+            Ok(()) => {
+                println!(
+                    "Successfuly downloaded {}{} to {}",
+                    format!("{}/{}", &repository.owner, &repository.name).cyan(),
+                    suffix.cyan(),
+                    &degit.path.green().bold(),
+                );
+                return Ok(());
+            }
+            Err(err) => {
+                let _ = fs::remove_dir_all(&degit.path);
+                eprintln!("{err:#}, falling back to git");
+            }
+        }
+    }
 
     println!(
-        "Cloning {} to {} from {}",
+        "Cloning {}{} to {} from {}",
         format!("{}/{}", &repository.owner, &repository.name).cyan(),
-        &clone_path.green().bold(),
+        suffix.cyan(),
+        &degit.path.green().bold(),
         &repository.host.white().bold()
     );
 
-    let output = git_process
-        .wait_with_output()
-        .with_context(|| "Failed to collect git clone output")?;
-    if !output.status.success() {
-        let exit_code = output.status.code().expect("Failed to get exit status");
-        let error = format!(
-            "git process failed, exited with code {}",
-            exit_code.clone().to_string().bold().red()
-        );
-        eprintln!("{error}");
-        exit(exit_code)
+    for mut command in degit.commands() {
+        let output = command
+            .stdin(Stdio::null())
+            .output()
+            .with_context(|| "Failed to spawn git process")?;
+        if !output.status.success() {
+            let exit_code = output.status.code().expect("Failed to get exit status");
+            let error = format!(
+                "git process failed, exited with code {}",
+                exit_code.clone().to_string().bold().red()
+            );
+            eprintln!("{error}");
+            // The destination didn't exist before, don't leave a partial clone behind
+            let _ = fs::remove_dir_all(&degit.path);
+            exit(exit_code)
+        }
     }
 
     println!(
-        "Successfuly cloned {} to {}",
+        "Successfuly cloned {}{} to {}",
         format!("{}/{}", &repository.owner, &repository.name).cyan(),
-        &clone_path.green().bold(),
+        suffix.cyan(),
+        &degit.path.green().bold(),
     );
 
-    fs::remove_dir_all(format!("{clone_path}/.git"))?;
+    degit.finish()
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hosts::{HostKind, Hosts, Site};
+    use std::sync::Once;
+
+    /// Built-in hosts plus the self-hosted one of the README.
+    fn install_hosts() {
+        static HOSTS: Once = Once::new();
+        HOSTS.call_once(|| {
+            let mut site = Site::new("acme", "git.acme.dev");
+            site.url = String::from("https://git.acme.dev:8443");
+            let mut hosts = Hosts::with_builtins();
+            hosts.register(HostKind::Gitlab.host(site));
+            hosts.install().unwrap();
+        });
+    }
+
+    fn repository(url: &str) -> Result<Repository> {
+        install_hosts();
+        Repository::from_url(url)
+    }
+
+    struct Case {
+        url: &'static str,
+        host: &'static str,
+        owner: &'static str,
+        name: &'static str,
+        subdir: Option<&'static str>,
+        git_ref: Option<&'static str>,
+        https: &'static str,
+        ssh: &'static str,
+    }
+
+    const GITHUB_REPO: Case = Case {
+        url: "",
+        host: "github",
+        owner: "user",
+        name: "repo",
+        subdir: None,
+        git_ref: None,
+        https: "https://github.com/user/repo",
+        ssh: "git@github.com:user/repo",
+    };
+
+    #[test]
+    fn parses_every_source_form() {
+        let cases = [
+            Case {
+                url: "user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "github:user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "github.com/user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "https://github.com/user/repo.git",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "https://token@github.com/user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "git@github.com:user/repo.git",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "ssh://git@github.com/user/repo.git",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "user/repo#v1.2.3",
+                git_ref: Some("v1.2.3"),
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "user/repo/templates/web#main",
+                subdir: Some("templates/web"),
+                git_ref: Some("main"),
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "https://github.com/user/repo.git/templates/web/",
+                subdir: Some("templates/web"),
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "user/repo#",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "gitlab:user/repo",
+                host: "gitlab",
+                https: "https://gitlab.com/user/repo",
+                ssh: "git@gitlab.com:user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "gitlab.com/user/repo",
+                host: "gitlab",
+                https: "https://gitlab.com/user/repo",
+                ssh: "git@gitlab.com:user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "https://gitlab.com/group/sub/repo.git/docs#main",
+                host: "gitlab",
+                owner: "group/sub",
+                subdir: Some("docs"),
+                git_ref: Some("main"),
+                https: "https://gitlab.com/group/sub/repo",
+                ssh: "git@gitlab.com:group/sub/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "gitlab.example.com/user/repo",
+                host: "gitlab.example.com",
+                https: "https://gitlab.example.com/user/repo",
+                ssh: "git@gitlab.example.com:user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "bitbucket:user/repo",
+                host: "bitbucket",
+                https: "https://bitbucket.org/user/repo",
+                ssh: "git@bitbucket.org:user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "codeberg:user/repo",
+                host: "codeberg",
+                https: "https://codeberg.org/user/repo",
+                ssh: "git@codeberg.org:user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "sr.ht:~user/repo",
+                host: "sr.ht",
+                owner: "~user",
+                https: "https://git.sr.ht/~user/repo",
+                ssh: "git@git.sr.ht:~user/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "https://dev.azure.com/org/project/_git/repo",
+                host: "azure",
+                owner: "org/project",
+                https: "https://org@dev.azure.com/org/project/_git/repo",
+                ssh: "git@ssh.dev.azure.com:v3/org/project/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "git@ssh.dev.azure.com:v3/org/project/repo",
+                host: "azure",
+                owner: "org/project",
+                https: "https://org@dev.azure.com/org/project/_git/repo",
+                ssh: "git@ssh.dev.azure.com:v3/org/project/repo",
+                ..GITHUB_REPO
+            },
+            Case {
+                url: "acme:team/repo",
+                host: "acme",
+                owner: "team",
+                https: "https://git.acme.dev:8443/team/repo",
+                ssh: "git@git.acme.dev:team/repo",
+                ..GITHUB_REPO
+            },
+        ];
+
+        for case in cases {
+            let url = case.url;
+            let repository = repository(url).unwrap_or_else(|err| panic!("{url}: {err:#}"));
+            assert_eq!(repository.host, case.host, "{url}");
+            assert_eq!(repository.owner, case.owner, "{url}");
+            assert_eq!(repository.name, case.name, "{url}");
+            assert_eq!(repository.subdir.as_deref(), case.subdir, "{url}");
+            assert_eq!(repository.git_ref.as_deref(), case.git_ref, "{url}");
+            assert_eq!(repository.https, case.https, "{url}");
+            assert_eq!(repository.ssh, case.ssh, "{url}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_sources() {
+        let cases = [
+            ("", "Invalid URL provided"),
+            ("repo", "Invalid URL provided"),
+            ("user/", "Invalid URL provided"),
+            // Only GitLab nests repositories, `sub/repo.git` isn't a directory
+            (
+                "https://github.com/group/sub/repo.git",
+                "Invalid URL provided",
+            ),
+            ("gitlab:group/sub/repo.git/docs.git", "Invalid URL provided"),
+            ("nowhere:user/repo", "Unknown host nowhere"),
+            (
+                "https://example.com/user/repo",
+                "Unsupported host example.com",
+            ),
+            ("example.com/user/repo", "Unsupported host example.com"),
+        ];
+
+        for (url, expected) in cases {
+            let Err(err) = repository(url) else {
+                panic!("{url} was accepted");
+            };
+            assert!(format!("{err:#}").starts_with(expected), "{url}: {err:#}");
+        }
+
+        install_hosts();
+        let opts = Options {
+            url: String::from("user/repo/../secrets"),
+            ..Default::default()
+        };
+        let err = Degit::new(&opts).unwrap_err();
+        assert!(
+            format!("{err:#}").starts_with("Invalid subdirectory ../secrets"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn keeps_ports_out_of_the_path() {
+        for url in [
+            "https://git.acme.dev:8443/team/repo",
+            "ssh://git@git.acme.dev:2222/team/repo.git",
+        ] {
+            let repository = repository(url).unwrap();
+            assert_eq!(repository.owner, "team", "{url}");
+            assert_eq!(repository.name, "repo", "{url}");
+            assert_eq!(repository.subdir, None, "{url}");
+            assert_eq!(repository.https, "https://git.acme.dev:8443/team/repo");
+        }
+    }
 }
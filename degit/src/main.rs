@@ -1,5 +1,5 @@
 use clap::Parser;
-use degit::options::Options;
+use degit::options::{Mode, Options};
 
 #[derive(Parser, Debug)]
 pub struct Args {
@@ -12,6 +12,18 @@ pub struct Args {
     /// Clone with ssh, the default is https
     #[arg(short, long)]
     pub ssh: bool,
+
+    /// Branch, tag or commit to be cloned, the same as appending "#<ref>" to the URL
+    #[arg(long = "ref", value_name = "REF")]
+    pub git_ref: Option<String>,
+
+    /// Directory of the repo to be extracted instead of the whole repo
+    #[arg(long)]
+    pub subdir: Option<String>,
+
+    /// How the repo is fetched
+    #[arg(short, long, default_value = "tar")]
+    pub mode: Mode,
 }
 
 impl From<Args> for Options {
@@ -20,6 +32,9 @@ impl From<Args> for Options {
             url: value.url,
             dest: value.dest,
             ssh: value.ssh,
+            git_ref: value.git_ref,
+            subdir: value.subdir,
+            mode: value.mode,
         }
     }
 }
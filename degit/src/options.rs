@@ -1,6 +1,23 @@
-#[derive(Debug)]
+use clap::ValueEnum;
+
+/// How the repository is fetched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Download the host's tarball, falling back to git when it isn't available
+    #[default]
+    Tar,
+    /// Shallow clone with git
+    Git,
+}
+
+#[derive(Debug, Default)]
 pub struct Options {
     pub url: String,
     pub dest: Option<String>,
     pub ssh: bool,
+    /// Overrides the ref given in the url.
+    pub git_ref: Option<String>,
+    /// Overrides the subdirectory given in the url.
+    pub subdir: Option<String>,
+    pub mode: Mode,
 }
@@ -0,0 +1,354 @@
+//! Tar mode against a local stand-in for a Gitea instance: archives are served
+//! from disk and git requests are handed to `git http-backend`.
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    sync::OnceLock,
+    thread,
+};
+
+use flate2::{write::GzEncoder, Compression};
+use tar::{Builder, EntryType, Header};
+
+use degit::{
+    hosts::{HostKind, Hosts, Site},
+    options::{Mode, Options},
+    Degit,
+};
+
+/// Root of the fixtures, `git` holds the repositories and `www` the archives.
+fn server() -> &'static Path {
+    static ROOT: OnceLock<PathBuf> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        let root = env::temp_dir().join(format!("degit-tests-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let repo = root.join("git/o/r");
+        fs::create_dir_all(repo.join("templates/web")).unwrap();
+        fs::write(repo.join("README.md"), "root\n").unwrap();
+        fs::write(repo.join("templates/web/index.html"), "web\n").unwrap();
+        commit(&repo);
+        let archive = git(
+            &repo,
+            &["archive", "--format=tar.gz", "--prefix=r-HEAD/", "HEAD"],
+        );
+        fs::create_dir_all(root.join("www/o/r/archive")).unwrap();
+        fs::write(root.join("www/o/r/archive/HEAD.tar.gz"), archive).unwrap();
+
+        // Only reachable with git, its archive is a 404
+        let repo = root.join("git/o/git-only");
+        fs::create_dir_all(&repo).unwrap();
+        fs::write(repo.join("README.md"), "git only\n").unwrap();
+        commit(&repo);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let served = root.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().map_while(Result::ok) {
+                let root = served.clone();
+                thread::spawn(move || handle(stream, &root));
+            }
+        });
+
+        let mut site = Site::new("local", "local.test");
+        site.url = format!("http://127.0.0.1:{port}");
+        let mut hosts = Hosts::with_builtins();
+        hosts.register(HostKind::Gitea.host(site));
+        hosts.install().unwrap();
+
+        root
+    })
+}
+
+fn git(dir: &Path, args: &[&str]) -> Vec<u8> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=degit", "-c", "user.email=degit@localhost"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    output.stdout
+}
+
+fn commit(repo: &Path) {
+    git(repo, &["init", "--quiet"]);
+    git(repo, &["add", "--all"]);
+    git(repo, &["commit", "--quiet", "--message", "Initial commit"]);
+}
+
+fn handle(stream: TcpStream, root: &Path) {
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request).unwrap();
+    let mut request = request.split_whitespace();
+    let method = request.next().unwrap_or_default().to_owned();
+    let target = request.next().unwrap_or_default().to_owned();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            break;
+        };
+        headers.push((name.to_ascii_lowercase(), value.trim().to_owned()));
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map_or("", |(_, value)| value.as_str())
+    };
+    let mut body = vec![0; header("content-length").parse().unwrap_or(0)];
+    reader.read_exact(&mut body).unwrap();
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let (status, response_headers, response) =
+        if path.ends_with("/info/refs") || path.ends_with("/git-upload-pack") {
+            let mut backend = Command::new("git")
+                .arg("http-backend")
+                .env("GIT_PROJECT_ROOT", root.join("git"))
+                .env("GIT_HTTP_EXPORT_ALL", "1")
+                .env("GIT_PROTOCOL", header("git-protocol"))
+                .env("REQUEST_METHOD", &method)
+                .env("PATH_INFO", path)
+                .env("QUERY_STRING", query)
+                .env("CONTENT_TYPE", header("content-type"))
+                .env("CONTENT_LENGTH", body.len().to_string())
+                .env("HTTP_CONTENT_ENCODING", header("content-encoding"))
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap();
+            backend.stdin.take().unwrap().write_all(&body).unwrap();
+            let output = backend.wait_with_output().unwrap();
+
+            let split = output
+                .stdout
+                .windows(4)
+                .position(|window| window == b"\r\n\r\n")
+                .unwrap();
+            let cgi_headers = String::from_utf8_lossy(&output.stdout[..split]).into_owned();
+            let mut status = String::from("200 OK");
+            let mut response_headers = String::new();
+            for line in cgi_headers.lines() {
+                match line.strip_prefix("Status: ") {
+                    Some(cgi_status) => status = cgi_status.to_owned(),
+                    None => response_headers.push_str(&format!("{line}\r\n")),
+                }
+            }
+            (
+                status,
+                response_headers,
+                output.stdout[split + 4..].to_vec(),
+            )
+        } else {
+            match fs::read(root.join("www").join(path.trim_start_matches('/'))) {
+                Ok(file) => (String::from("200 OK"), String::new(), file),
+                Err(_) => (String::from("404 Not Found"), String::new(), vec![]),
+            }
+        };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n{response_headers}Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.len()
+    )
+    .unwrap();
+    stream.write_all(&response).unwrap();
+}
+
+fn options(url: &str, dest: &str) -> Options {
+    let dest = server().join("out").join(dest);
+    Options {
+        url: url.to_owned(),
+        dest: Some(dest.display().to_string()),
+        ..Default::default()
+    }
+}
+
+fn files(dir: &str) -> Vec<String> {
+    fn walk(dir: &Path, base: &Path, files: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(&path, base, files);
+            } else {
+                let relative = path.strip_prefix(base).unwrap();
+                files.push(relative.display().to_string());
+            }
+        }
+    }
+
+    let dir = server().join("out").join(dir);
+    let mut files = vec![];
+    walk(&dir, &dir, &mut files);
+    files.sort();
+    files
+}
+
+#[test]
+fn extracts_the_archive() {
+    let degit = Degit::new(&options("local:o/r", "plain")).unwrap();
+    degit.download().unwrap();
+
+    assert_eq!(files("plain"), ["README.md", "templates/web/index.html"]);
+}
+
+#[test]
+fn extracts_only_the_subdirectory() {
+    let degit = Degit::new(&options("local:o/r/templates/web", "subdir")).unwrap();
+    degit.download().unwrap();
+
+    assert_eq!(files("subdir"), ["index.html"]);
+}
+
+#[test]
+fn fails_on_a_missing_subdirectory() {
+    let degit = Degit::new(&options("local:o/r/nope", "missing")).unwrap();
+    let err = degit.download().unwrap_err();
+
+    assert!(
+        format!("{err:#}").contains("nope isn't a directory of o/r"),
+        "{err:#}"
+    );
+}
+
+#[test]
+fn falls_back_to_git_on_a_404() {
+    let opts = options("local:o/git-only", "fallback");
+    let err = Degit::new(&opts).unwrap().download().unwrap_err();
+    assert!(format!("{err:#}").contains("status code 404"), "{err:#}");
+    let _ = fs::remove_dir_all(opts.dest.as_ref().unwrap());
+
+    assert_eq!(opts.mode, Mode::Tar);
+    degit::run(opts).unwrap();
+
+    assert_eq!(files("fallback"), ["README.md"]);
+}
+
+/// Archive with entries `(path, type, link name or content)`, paths are
+/// written as is so they can escape.
+fn crafted(entries: &[(&str, EntryType, &str)]) -> Vec<u8> {
+    let mut builder = Builder::new(GzEncoder::new(vec![], Compression::default()));
+    for &(path, entry_type, value) in entries {
+        let mut header = Header::new_gnu();
+        header.as_gnu_mut().unwrap().name[..path.len()].copy_from_slice(path.as_bytes());
+        header.set_entry_type(entry_type);
+        header.set_mode(0o644);
+        let data = if entry_type == EntryType::Regular {
+            value.as_bytes()
+        } else {
+            header.set_link_name(value).unwrap();
+            &[]
+        };
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder.append(&header, data).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+/// Extracts an archive built from `entries` and checks it was refused with
+/// `expected`, without writing to the `outside` directory they point at.
+fn refuses(name: &str, entries: impl Fn(&str) -> Vec<(String, EntryType, String)>, expected: &str) {
+    let opts = options("local:o/r", name);
+    let outside = server().join(format!("outside-{name}"));
+    fs::create_dir_all(&outside).unwrap();
+    let entries = entries(&outside.display().to_string());
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|(path, entry_type, value)| (path.as_str(), *entry_type, value.as_str()))
+        .collect();
+
+    let degit = Degit::new(&opts).unwrap();
+    let err = degit.extract(crafted(&entries).as_slice()).unwrap_err();
+
+    assert!(format!("{err:#}").contains(expected), "{err:#}");
+    assert_eq!(fs::read_dir(&outside).unwrap().count(), 0);
+    assert!(!server().join("out/evil").exists());
+}
+
+#[test]
+fn refuses_parent_directories() {
+    refuses(
+        "parent",
+        |_| {
+            vec![
+                (
+                    String::from("r-HEAD/README.md"),
+                    EntryType::Regular,
+                    String::from("root"),
+                ),
+                (
+                    String::from("r-HEAD/../evil"),
+                    EntryType::Regular,
+                    String::from("evil"),
+                ),
+            ]
+        },
+        "Archive entry r-HEAD/../evil escapes the destination",
+    );
+}
+
+#[test]
+fn refuses_writing_through_a_symlink() {
+    refuses(
+        "symlink",
+        |outside| {
+            vec![
+                (
+                    String::from("r-HEAD/x"),
+                    EntryType::Symlink,
+                    outside.to_owned(),
+                ),
+                (
+                    String::from("r-HEAD/x/evil"),
+                    EntryType::Regular,
+                    String::from("evil"),
+                ),
+            ]
+        },
+        "Archive entry r-HEAD/x/evil escapes the destination",
+    );
+}
+
+#[test]
+fn refuses_hard_links() {
+    refuses(
+        "hard-link",
+        |outside| {
+            vec![(
+                String::from("r-HEAD/evil"),
+                EntryType::Link,
+                format!("{outside}/target"),
+            )]
+        },
+        "Archive entry r-HEAD/evil is a hard link",
+    );
+}
+
+#[test]
+fn keeps_symlinks_inside_the_repository() {
+    let opts = options("local:o/r", "inner-symlink");
+    let archive = crafted(&[
+        ("r-HEAD/README.md", EntryType::Regular, "root"),
+        ("r-HEAD/LINK.md", EntryType::Symlink, "README.md"),
+    ]);
+    Degit::new(&opts)
+        .unwrap()
+        .extract(archive.as_slice())
+        .unwrap();
+
+    let link = server().join("out/inner-symlink/LINK.md");
+    assert_eq!(fs::read_link(&link).unwrap(), Path::new("README.md"));
+    assert_eq!(fs::read_to_string(link).unwrap(), "root");
+}
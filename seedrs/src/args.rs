@@ -1,32 +1,136 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::{num::NonZeroUsize, path::PathBuf};
 
-use seedrs::{Options, PackageManager, Tech};
+use seedrs::{Options, OutputFormat, PackageManager, ProjectKind, Tech};
 
 #[derive(Parser, Debug)]
 pub struct Args {
-    /// Technologies that will be used in the project
-    #[arg(short, long, value_delimiter = ',')]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Technologies that will be used in the project, one project is created for each
+    #[arg(short, long, value_delimiter = ',', global = true)]
     techs: Option<Vec<Tech>>,
 
     /// PackageManager to be used
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     package_manager: Option<PackageManager>,
 
+    /// Names of each project, in the same order as the techs
+    #[arg(long, value_delimiter = ',', global = true)]
+    names: Option<Vec<String>>,
+
+    /// Git templates of each project, in the same order as the techs (leave empty for none)
+    #[arg(long, value_delimiter = ',', global = true)]
+    templates: Option<Vec<String>>,
+
+    /// Initialize a git repository in every project
+    #[arg(long, conflicts_with = "no_git", global = true)]
+    git: bool,
+
+    /// Don't initialize a git repository in any project
+    #[arg(long, global = true)]
+    no_git: bool,
+
+    /// Install dependencies of every project
+    #[arg(long, conflicts_with = "no_install", global = true)]
+    install: bool,
+
+    /// Don't install dependencies of any project
+    #[arg(long, global = true)]
+    no_install: bool,
+
+    /// Manifest (.toml or .json) describing every project to be created
+    #[arg(short, long, conflicts_with_all = ["techs", "names", "templates"])]
+    manifest: Option<PathBuf>,
+
+    /// Save every answer to a manifest (.toml or .json) that can be replayed with --manifest
+    #[arg(long, value_name = "MANIFEST")]
+    save_answers: Option<PathBuf>,
+
+    /// Print every command that would be executed instead of running them
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "human"
+    )]
+    dry_run: Option<OutputFormat>,
+
+    /// How many projects can be created at the same time, defaults to the number of CPUs
+    #[arg(short, long, global = true)]
+    jobs: Option<NonZeroUsize>,
+
+    /// Print the output of every command as it runs, labeled by project
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Format of the summary printed once every project is done
+    #[arg(long, value_name = "FORMAT", default_value = "human", global = true)]
+    report: OutputFormat,
+
+    /// Keep the directories of failed projects instead of removing them
+    #[arg(long, global = true)]
+    keep_on_failure: bool,
+
+    /// Never prompt, fail if a required value wasn't provided
+    #[arg(long, global = true)]
+    no_input: bool,
+
     /// Project name
     name: Option<String>,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Check which of the tools used by seedrs are available
+    Doctor,
+    /// Add new projects to an existing workspace
+    Add {
+        /// Kind of the new projects
+        kind: ProjectKind,
+        /// Prefix directory of the workspace
+        #[arg(default_value = ".")]
+        prefix: String,
+        /// How many projects will be added, prompted when missing
+        #[arg(long)]
+        count: Option<usize>,
+    },
+    /// Retry the steps of a previous run that failed or never ran
+    Resume {
+        /// Prefix directory of the run
+        #[arg(default_value = ".")]
+        prefix: String,
+    },
+}
+
+fn flag_pair(yes: bool, no: bool) -> Option<bool> {
+    match (yes, no) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
 impl From<Args> for Options {
     fn from(value: Args) -> Self {
-        let techs: Vec<Tech> = if let Some(techs) = value.techs {
-            techs
-        } else {
-            vec![]
-        };
         Self {
-            techs,
+            techs: value.techs.unwrap_or_default(),
             project_prefix: value.name,
             package_manager: value.package_manager,
+            names: value.names.unwrap_or_default(),
+            templates: value.templates.unwrap_or_default(),
+            init_git: flag_pair(value.git, value.no_git),
+            should_install: flag_pair(value.install, value.no_install),
+            no_input: value.no_input,
+            manifest: value.manifest,
+            save_answers: value.save_answers,
+            dry_run: value.dry_run,
+            jobs: value.jobs,
+            verbose: value.verbose,
+            report: value.report,
+            keep_on_failure: value.keep_on_failure,
         }
     }
 }
@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::{
+    fs,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{logs, pipeline::Step, state::StateFile, Report};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Makes Ctrl-C stop the run instead of killing seedrs, so whatever was created
+/// can still be rolled back.
+pub fn handle_interrupts() -> Result<()> {
+    ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst))
+        .context("Failed to set the Ctrl-C handler")
+}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+fn remove(path: &Path) -> Result<()> {
+    if path.try_exists()? {
+        fs::remove_dir_all(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        eprintln!("Removed {}", path.display().to_string().bold());
+    }
+    Ok(())
+}
+
+/// Removes the whole prefix, only for prefixes created by this run.
+pub fn remove_prefix(project_prefix: &str) -> Result<()> {
+    remove(Path::new(project_prefix))?;
+    eprintln!("Rerun with --keep-on-failure to inspect what was left behind");
+    Ok(())
+}
+
+/// Undoes a run that didn't fully succeed or was interrupted. Failed projects
+/// that got past their init are kept for `seedrs resume`, the others have their
+/// directory removed, or the whole prefix when this run `created_prefix` and no
+/// project there completed anything. Returns whether the prefix is still there.
+pub fn rollback(report: &mut Report, state: &StateFile, created_prefix: bool) -> Result<bool> {
+    let failed: Vec<_> = report
+        .projects
+        .iter()
+        .filter(|project| !project.succeeded() && !state.is_completed(&project.name, Step::Init))
+        .map(|project| project.name.clone())
+        .collect();
+    for name in &failed {
+        state.reset(name)?;
+    }
+
+    if created_prefix && !state.has_progress() {
+        let logs = logs::preserve(&report.prefix)?;
+        report.move_logs(&logs::logs_dir(&report.prefix), &logs);
+        remove_prefix(&report.prefix)?;
+        eprintln!("The logs of this run were kept in {}", logs.display());
+        return Ok(false);
+    }
+    for name in failed {
+        remove(&Path::new(&report.prefix).join(name))?;
+    }
+    Ok(true)
+}
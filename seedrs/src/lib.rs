@@ -1,213 +1,334 @@
+mod cleanup;
+mod logs;
+mod manifest;
 mod options;
 mod package_managers;
+mod pipeline;
+mod plan;
+mod preflight;
+mod progress;
 mod projects;
+mod report;
+mod state;
 mod techs;
 mod ui;
 
-use std::fs;
+use std::{fs, num::NonZeroUsize, path::Path, thread};
 
-use anyhow::Result;
-use projects::{Project, ProjectBuilder};
+use anyhow::{bail, Result};
+use projects::{KindOrders, Project, ProjectBuilder};
+use state::StateFile;
 
-pub use options::Options;
+pub use manifest::{Manifest, ManifestProject};
+pub use options::{Options, OutputFormat};
 pub use package_managers::PackageManager;
+pub use pipeline::Step;
+pub use plan::Plan;
+pub use preflight::doctor;
 pub use projects::ProjectKind;
-pub use techs::Tech;
+pub use report::{PartialFailure, ProjectReport, Report, StepReport, StepStatus};
+pub use techs::{registry, Tech, TechDefinition, TechRegistry};
 
-pub fn run(/* opts: Options */) -> Result<()> {
-    // let Options {
-    //     mut project_prefix,
-    //     ..
-    //     package_manager,
-    //     mut techs,
-    // } = opts;
-
-    // if project_prefix.is_none() {
-    //     project_prefix = Some(ui::render_project_naming()?);
-    // }
-    ui::render_welcome();
-
-    let project_prefix = ui::render_project_naming_prompt()?;
-
-    let (apis, webs, apps) = ui::render_project_definition_prompt()?;
+pub fn run(opts: Options) -> Result<()> {
+    let (project_prefix, projects, corepack) = if let Some(path) = &opts.manifest {
+        let manifest = Manifest::load(path)?;
+        let project_prefix = opts
+            .project_prefix
+            .clone()
+            .unwrap_or_else(|| manifest.prefix.clone());
+        let projects = manifest.build_projects(&project_prefix)?;
+        (project_prefix, projects, vec![])
+    } else {
+        if !opts.no_input {
+            ui::render_welcome();
+        }
 
-    let sum = (apis + webs + apps) as usize;
+        let project_prefix = match &opts.project_prefix {
+            Some(prefix) => prefix.clone(),
+            None if opts.no_input => String::from(ui::DEFAULT_PROJECT_PREFIX),
+            None => ui::render_project_naming_prompt()?,
+        };
+        let (projects, corepack) = collect_projects(&project_prefix, &opts)?;
+        (project_prefix, projects, corepack)
+    };
 
-    if sum == 0 {
+    if projects.is_empty() {
         println!("You didn't specify any number of projects, exiting.");
         return Ok(());
     }
 
-    let mut projects: Vec<Project> = Vec::with_capacity(sum);
-
-    // TODO: make a single loop for all types
-    for i in 0..webs {
-        let name = ui::render_naming_prompt(&project_prefix, ProjectKind::Web, i + 1)?;
-        let template = ui::render_template_choice_prompt(&name)?;
-        let init_git = ui::render_git_init_prompt(&name)?;
-        let install_deps = ui::render_install_dependencies_prompt(&name)?;
-        let tech = ui::render_tech_selection_prompt(&name, ProjectKind::Web)?;
-        let package_manager = ui::render_package_manager_selection_prompt(&name, &tech)?;
+    if let Some(path) = &opts.save_answers {
+        Manifest::from_projects(&project_prefix, &projects).save(path)?;
+        eprintln!("Answers saved to {}", path.display());
+    }
 
-        projects.push(
-            ProjectBuilder::default()
-                .name(name)
-                .template(template)
-                .tech(tech)
-                .init_git(init_git)
-                .should_install(install_deps)
-                .package_manager(package_manager)
-                .build()?,
-        );
+    if let Some(format) = &opts.dry_run {
+        return Plan::build(&project_prefix, &projects)?.print(format);
+    }
 
-        println!("\n");
+    for package_manager in corepack {
+        package_manager.enable_with_corepack()?;
     }
 
-    for i in 0..apis {
-        let name = ui::render_naming_prompt(&project_prefix, ProjectKind::Api, i + 1)?;
-        let template = ui::render_template_choice_prompt(&name)?;
-        let init_git = ui::render_git_init_prompt(&name)?;
-        let install_deps = ui::render_install_dependencies_prompt(&name)?;
-        let tech = ui::render_tech_selection_prompt(&name, ProjectKind::Api)?;
-        let package_manager = ui::render_package_manager_selection_prompt(&name, &tech)?;
+    preflight::preflight(&project_prefix, &projects)?;
 
-        projects.push(
-            ProjectBuilder::default()
-                .name(name)
-                .template(template)
-                .tech(tech)
-                .init_git(init_git)
-                .should_install(install_deps)
-                .package_manager(package_manager)
-                .build()?,
-        );
+    cleanup::handle_interrupts()?;
+    fs::create_dir(&project_prefix)?;
 
-        println!("\n");
-    }
+    let state = match StateFile::create(&project_prefix, &projects) {
+        Ok(state) => state,
+        Err(err) => {
+            cleanup::remove_prefix(&project_prefix)?;
+            return Err(err);
+        }
+    };
+    execute(&project_prefix, &projects, &state, true, &opts)
+}
 
-    for i in 0..apps {
-        let name = ui::render_naming_prompt(&project_prefix, ProjectKind::Mobile, i + 1)?;
-        let template = ui::render_template_choice_prompt(&name)?;
-        let init_git = ui::render_git_init_prompt(&name)?;
-        let install_deps = ui::render_install_dependencies_prompt(&name)?;
-        let tech = ui::render_tech_selection_prompt(&name, ProjectKind::Mobile)?;
-        let package_manager = ui::render_package_manager_selection_prompt(&name, &tech)?;
+/// Runs the steps of `projects` that didn't complete yet in the workspace at
+/// `project_prefix`.
+pub fn resume(project_prefix: &str, opts: Options) -> Result<()> {
+    let state = StateFile::load(project_prefix)?;
+    let projects = state.manifest().build_projects(project_prefix)?;
 
-        projects.push(
-            ProjectBuilder::default()
-                .name(name)
-                .template(template)
-                .tech(tech)
-                .init_git(init_git)
-                .should_install(install_deps)
-                .package_manager(package_manager)
-                .build()?,
-        );
-
-        println!("\n");
+    let pending = projects.iter().any(|project| {
+        project
+            .steps()
+            .into_iter()
+            .any(|step| !state.is_completed(&project.name, step))
+    });
+    if !pending {
+        eprintln!("Every project in {project_prefix} is already complete, nothing to resume.");
+        return Ok(());
     }
 
-    fs::create_dir(&project_prefix)?;
+    preflight::preflight(project_prefix, &projects)?;
+    cleanup::handle_interrupts()?;
+    execute(project_prefix, &projects, &state, false, &opts)
+}
 
-    let mut processes = Vec::new();
+/// Runs the pipeline of `projects` and rolls back what failed, the prefix itself
+/// is only removed when `created_prefix` says this run created it.
+fn execute(
+    project_prefix: &str,
+    projects: &[Project],
+    state: &StateFile,
+    created_prefix: bool,
+    opts: &Options,
+) -> Result<()> {
+    let jobs = opts
+        .jobs
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    let mut report = pipeline::run(project_prefix, projects, state, jobs, opts.verbose)?;
 
-    for project in projects.iter() {
-        match project.spawn_init_command(&project_prefix) {
-            Ok(child) => processes.push((project, child)),
-            Err(err) => {
-                eprintln!(
-                    "Failed to spawn init command for {}. Error: {}",
-                    project.name, err
-                );
-            }
+    // Rolling back may move the logs the errors point at
+    let failed = report.failures();
+    let kept = opts.keep_on_failure
+        || failed == 0
+        || cleanup::rollback(&mut report, state, created_prefix)?;
+    report.print_errors();
+    report.print(&opts.report)?;
+    if cleanup::interrupted() {
+        bail!("Interrupted");
+    }
+    if failed > 0 {
+        if kept {
+            eprintln!("Run `seedrs resume {project_prefix}` to retry the failed steps");
         }
+        return Err(PartialFailure {
+            failed,
+            total: projects.len(),
+        }
+        .into());
     }
 
-    let mut install_deps = Vec::new();
-    let mut init_git = Vec::new();
-
-    for (project, proc) in processes.into_iter() {
-        match proc.wait_with_output() {
-            Ok(output) => {
-                if output.status.success() {
-                    if project.init_git {
-                        init_git.push(project);
-                    }
-                    if project.should_install {
-                        install_deps.push(project);
-                    }
-                } else if let Some(code) = output.status.code() {
-                    eprintln!(
-                        "Error: init command for {} exited with {}",
-                        project.name, code,
-                    );
-                } else {
-                    eprintln!(
-                        "Error: init command for {} exited with no exit code",
-                        project.name,
-                    );
-                }
-            }
-            Err(err) => {
-                eprintln!(
-                    "Failed to run init command for {}. Error: {}",
-                    project.name, err
-                );
-            }
+    Ok(())
+}
+
+/// Adds new projects of `kind` to the workspace at `path`, `count` of them
+/// unless the techs were given.
+pub fn add(path: &str, kind: ProjectKind, count: Option<usize>, opts: Options) -> Result<()> {
+    let state = StateFile::load(path)?;
+    let manifest = state.manifest();
+
+    // Default names continue from the projects already there
+    let mut orders = KindOrders::default();
+    for entry in &manifest.projects {
+        if let Some(kind) = entry.resolved_kind() {
+            orders.next(kind);
         }
     }
 
-    let mut processes = Vec::new();
+    let slots: Vec<(ProjectKind, Option<Tech>)> = if !opts.techs.is_empty() {
+        if let Some(tech) = opts.techs.iter().find(|tech| tech.kind() != kind) {
+            bail!("{tech} isn't a {kind} tech");
+        }
+        opts.techs
+            .iter()
+            .map(|tech| (kind, Some(tech.clone())))
+            .collect()
+    } else {
+        let count = match count {
+            Some(count) => count,
+            None if opts.no_input => 1,
+            None => ui::render_project_count_prompt(
+                &format!("How many {kind}s will be added to {}?", manifest.prefix),
+                1,
+            )? as usize,
+        };
+        vec![(kind, None); count]
+    };
 
-    for project in install_deps.into_iter() {
-        match project.spawn_git_init_command(&project_prefix) {
-            Ok(child) => processes.push((project, child, "git init")),
-            Err(err) => {
-                eprintln!(
-                    "Failed to spawn git initialization process for project {}. Error: {}",
-                    project.name, err
-                )
-            }
+    let (projects, corepack) = define_projects(&manifest.prefix, slots, orders, &opts)?;
+    if projects.is_empty() {
+        println!("You didn't specify any number of projects, exiting.");
+        return Ok(());
+    }
+    for project in &projects {
+        let taken = manifest
+            .projects
+            .iter()
+            .any(|entry| entry.name.as_ref() == Some(&project.name));
+        if taken || Path::new(path).join(&project.name).try_exists()? {
+            bail!("{} already exists in {path}", project.name);
         }
     }
 
-    for project in init_git.into_iter() {
-        match project.spawn_install_deps_command(&project_prefix) {
-            Ok(child) => processes.push((project, child, "install dependencies")),
-            Err(err) => {
-                eprintln!(
-                    "Failed to spawn dependencies install process for project {}. Error: {}",
-                    project.name, err
-                )
-            }
-        }
+    for package_manager in corepack {
+        package_manager.enable_with_corepack()?;
     }
 
-    for (project, proc, proc_type) in processes.into_iter() {
-        match proc.wait_with_output() {
-            Ok(output) => {
-                if output.status.success() {
-                    println!("Successfully {} for {}", proc_type, project.name);
-                } else if let Some(code) = output.status.code() {
-                    eprintln!(
-                        "Error: {} command for {} exited with {}",
-                        proc_type, project.name, code,
-                    );
-                } else {
-                    eprintln!(
-                        "Error: {} for {} exited with no exit code",
-                        proc_type, project.name,
-                    );
-                }
+    preflight::preflight(path, &projects)?;
+    state.add_projects(&projects)?;
+    cleanup::handle_interrupts()?;
+    execute(path, &projects, &state, false, &opts)
+}
+
+/// Resolves every project from the options, prompting for what's missing. Also
+/// returns the package managers the user agreed to enable with corepack.
+fn collect_projects(
+    project_prefix: &str,
+    opts: &Options,
+) -> Result<(Vec<Project>, Vec<PackageManager>)> {
+    let slots: Vec<(ProjectKind, Option<Tech>)> = if !opts.techs.is_empty() {
+        opts.techs
+            .iter()
+            .map(|tech| (tech.kind(), Some(tech.clone())))
+            .collect()
+    } else if opts.no_input {
+        bail!("No techs were provided, use --techs to describe the projects when running with --no-input");
+    } else {
+        let (apis, webs, apps) = ui::render_project_definition_prompt()?;
+        let mut slots = Vec::with_capacity((apis + webs + apps) as usize);
+        slots.extend((0..webs).map(|_| (ProjectKind::Web, None)));
+        slots.extend((0..apis).map(|_| (ProjectKind::Api, None)));
+        slots.extend((0..apps).map(|_| (ProjectKind::Mobile, None)));
+        slots
+    };
+
+    define_projects(project_prefix, slots, KindOrders::default(), opts)
+}
+
+/// Resolves a project for every `(kind, tech)` slot, numbering default names
+/// from `orders`.
+fn define_projects(
+    project_prefix: &str,
+    slots: Vec<(ProjectKind, Option<Tech>)>,
+    mut orders: KindOrders,
+    opts: &Options,
+) -> Result<(Vec<Project>, Vec<PackageManager>)> {
+    if opts.names.len() > slots.len() {
+        bail!(
+            "Received {} names for {} projects",
+            opts.names.len(),
+            slots.len()
+        );
+    }
+    if opts.templates.len() > slots.len() {
+        bail!(
+            "Received {} templates for {} projects",
+            opts.templates.len(),
+            slots.len()
+        );
+    }
+
+    let mut projects: Vec<Project> = Vec::with_capacity(slots.len());
+    let mut corepack = Vec::new();
+
+    for (index, (kind, tech)) in slots.into_iter().enumerate() {
+        let order = orders.next(kind);
+
+        let name = match opts.names.get(index) {
+            Some(name) => name.clone(),
+            None if opts.no_input => kind.default_name(project_prefix, order),
+            None => ui::render_naming_prompt(project_prefix, kind, order)?,
+        };
+        // Projects share their directory, state entry and logs by name
+        if projects.iter().any(|project| project.name == name) {
+            bail!("{name} is used by more than one project, project names must be unique");
+        }
+        let template = match opts.templates.get(index) {
+            Some(template) if template.is_empty() => None,
+            Some(template) => Some(template.clone()),
+            None if opts.no_input => None,
+            None => ui::render_template_choice_prompt(&name)?,
+        };
+        let init_git = match opts.init_git {
+            Some(init_git) => init_git,
+            None if opts.no_input => true,
+            None => ui::render_git_init_prompt(&name)?,
+        };
+        let install_deps = match opts.should_install {
+            Some(install_deps) => install_deps,
+            None if opts.no_input => true,
+            None => ui::render_install_dependencies_prompt(&name)?,
+        };
+        let tech = match tech {
+            Some(tech) => tech,
+            None if opts.no_input => {
+                bail!("No tech was provided for {name}, use --techs when running with --no-input")
             }
-            Err(err) => {
-                eprintln!(
-                    "Failed to run {} for {}. Error: {}",
-                    proc_type, project.name, err
-                );
+            None => ui::render_tech_selection_prompt(&name, kind)?,
+        };
+        let package_managers = tech.get_package_managers();
+        let package_manager = match &opts.package_manager {
+            Some(package_manager) if package_managers.contains(package_manager) => {
+                package_manager.clone()
             }
+            // Techs of other ecosystems don't conflict with a package manager meant for JS projects
+            _ if package_managers.len() == 1 => package_managers[0].clone(),
+            Some(package_manager) => {
+                bail!("{tech} projects can't be created with {package_manager}");
+            }
+            None if opts.no_input => {
+                bail!("No package manager was provided for {name}, use --package-manager when running with --no-input")
+            }
+            None => {
+                let (package_manager, enable) =
+                    ui::render_package_manager_selection_prompt(&name, &tech)?;
+                if enable && !corepack.contains(&package_manager) {
+                    corepack.push(package_manager.clone());
+                }
+                package_manager
+            }
+        };
+
+        projects.push(
+            ProjectBuilder::default()
+                .name(name)
+                .template(template)
+                .tech(tech)
+                .init_git(init_git)
+                .should_install(install_deps)
+                .package_manager(package_manager)
+                .build()?,
+        );
+
+        if !opts.no_input {
+            println!("\n");
         }
     }
 
-    Ok(())
+    Ok((projects, corepack))
 }
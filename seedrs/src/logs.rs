@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::{
+    env,
+    fs::{self, File},
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{cleanup, pipeline::Step};
+
+const WAIT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Directory, relative to the project prefix, holding the output of every step.
+pub const LOGS_DIR: &str = ".seedrs/logs";
+
+pub fn logs_dir(project_prefix: &str) -> PathBuf {
+    Path::new(project_prefix).join(LOGS_DIR)
+}
+
+/// Moves the logs of `project_prefix` to the system temp directory, so they
+/// outlive a rollback removing the whole prefix. Returns where they are now.
+pub fn preserve(project_prefix: &str) -> Result<PathBuf> {
+    let logs = logs_dir(project_prefix);
+    let name = Path::new(project_prefix)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("seedrs");
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let kept = env::temp_dir()
+        .join("seedrs-logs")
+        .join(format!("{name}-{started}"));
+    fs::create_dir_all(&kept).with_context(|| format!("Failed to create {}", kept.display()))?;
+
+    for entry in
+        fs::read_dir(&logs).with_context(|| format!("Failed to read {}", logs.display()))?
+    {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        let target = kept.join(file_name);
+        // The temp directory may be on another filesystem
+        if fs::rename(&path, &target).is_err() {
+            fs::copy(&path, &target)
+                .with_context(|| format!("Failed to keep the log {}", path.display()))?;
+        }
+    }
+    Ok(kept)
+}
+
+/// Kills `child` along with everything it spawned, which may be holding its
+/// output open.
+#[cfg(unix)]
+fn terminate(child: &mut Child) {
+    // SAFETY: kill has no memory safety requirements, the group id comes from
+    // a child spawned with `process_group(0)`
+    unsafe {
+        libc::kill(-(child.id() as i32), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) {
+    let _ = child.kill();
+}
+
+/// Output of one step of a project, written to `<prefix>/.seedrs/logs/<project>-<step>.log`.
+#[derive(Debug)]
+pub struct StepLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl StepLog {
+    pub fn create(project_prefix: &str, project: &str, step: Step) -> Result<Self> {
+        let path = logs_dir(project_prefix).join(format!("{project}-{}.log", step.id()));
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to create log {}", path.display()))?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Runs `command` writing both its stdout and stderr to the log. When a
+    /// `label` is given every line is also printed, prefixed by it.
+    pub fn run(&self, command: &mut Command, label: Option<&str>) -> Result<ExitStatus> {
+        self.write_line(&format!(
+            "$ {} {}",
+            command.get_program().to_string_lossy(),
+            command
+                .get_args()
+                .map(|arg| arg.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+
+        // In its own group so an interruption can stop every process it spawned
+        #[cfg(unix)]
+        command.process_group(0);
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+
+        thread::scope(|scope| {
+            scope.spawn(|| self.forward(stdout, label));
+            scope.spawn(|| self.forward(stderr, label));
+
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    return Ok(status);
+                }
+                if cleanup::interrupted() {
+                    terminate(&mut child);
+                }
+                thread::sleep(WAIT_INTERVAL);
+            }
+        })
+    }
+
+    fn forward(&self, output: impl Read, label: Option<&str>) {
+        for line in BufReader::new(output).split(b'\n').map_while(Result::ok) {
+            let line = String::from_utf8_lossy(&line);
+            if let Some(label) = label {
+                eprintln!("{} {line}", format!("[{label}]").cyan());
+            }
+            self.write_line(&line);
+        }
+    }
+
+    fn write_line(&self, line: &str) {
+        let mut file = self.file.lock().expect("log lock poisoned");
+        // A log that can't be written shouldn't fail the step itself
+        let _ = writeln!(file, "{line}");
+    }
+
+    /// Last `lines` lines of the log.
+    pub fn tail(&self, lines: usize) -> String {
+        let content = fs::read_to_string(&self.path).unwrap_or_default();
+        let content: Vec<&str> = content.trim_end().lines().collect();
+        content[content.len().saturating_sub(lines)..].join("\n")
+    }
+}
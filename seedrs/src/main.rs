@@ -1,16 +1,31 @@
 mod args;
 
 use anyhow::Result;
-// use args::Args;
-// use clap::Parser;
-// use seedrs::Options;
+use args::{Args, Command};
+use clap::Parser;
+use seedrs::{Options, PartialFailure};
 use std::process::exit;
 
 fn main() -> Result<()> {
-    // let args = Args::parse();
+    let mut args = Args::parse();
 
-    if let Err(err) = seedrs::run(/* Options::from(args)*/ ) {
-        eprintln!("An error ocurred: {err}");
+    let result = match args.command.take() {
+        Some(Command::Doctor) => seedrs::doctor(),
+        Some(Command::Add {
+            kind,
+            prefix,
+            count,
+        }) => seedrs::add(&prefix, kind, count, Options::from(args)),
+        Some(Command::Resume { prefix }) => seedrs::resume(&prefix, Options::from(args)),
+        None => seedrs::run(Options::from(args)),
+    };
+
+    if let Err(err) = result {
+        if err.is::<PartialFailure>() {
+            eprintln!("{err}");
+            exit(PartialFailure::EXIT_CODE)
+        }
+        eprintln!("An error ocurred: {err:#}");
         exit(1)
     }
 
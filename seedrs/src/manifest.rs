@@ -0,0 +1,358 @@
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use crate::{
+    projects::{KindOrders, Project, ProjectBuilder},
+    PackageManager, ProjectKind, Tech,
+};
+
+/// Declarative description of a whole workspace, read from a `seedrs.toml`
+/// (or `.json`) file instead of answering the prompts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Manifest {
+    pub prefix: String,
+    #[serde(default)]
+    pub projects: Vec<ManifestProject>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ManifestProject {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ProjectKind>,
+    pub tech: String,
+    pub package_manager: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default = "default_true")]
+    pub init_git: bool,
+    #[serde(default = "default_true")]
+    pub install: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            _ => bail!(
+                "Unsupported manifest format for {}, use a .toml or .json file",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl From<&Project> for ManifestProject {
+    fn from(project: &Project) -> Self {
+        let tech = project.tech();
+
+        Self {
+            name: Some(project.name.clone()),
+            kind: Some(tech.kind()),
+            tech: tech.id().to_owned(),
+            package_manager: project.package_manager().to_string(),
+            template: project.template().map(String::from),
+            init_git: project.init_git,
+            install: project.should_install,
+        }
+    }
+}
+
+impl ManifestProject {
+    /// Declared kind, falling back to the one of the tech when it's known.
+    pub fn resolved_kind(&self) -> Option<ProjectKind> {
+        self.kind
+            .or_else(|| self.tech.parse::<Tech>().ok().map(|tech| tech.kind()))
+    }
+}
+
+impl Manifest {
+    pub fn from_projects(prefix: &str, projects: &[Project]) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+            projects: projects.iter().map(ManifestProject::from).collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let format = Format::from_path(path)?;
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest {}", path.display()))?;
+
+        let manifest = match format {
+            Format::Toml => toml::from_str(&content)
+                .with_context(|| format!("Invalid manifest {}", path.display()))?,
+            Format::Json => serde_json::from_str(&content)
+                .with_context(|| format!("Invalid manifest {}", path.display()))?,
+        };
+
+        Ok(manifest)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = match Format::from_path(path)? {
+            Format::Toml => toml::to_string_pretty(self)?,
+            Format::Json => serde_json::to_string_pretty(self)?,
+        };
+
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write manifest {}", path.display()))
+    }
+
+    /// Validates every entry and turns them into the projects that will be created
+    /// inside `project_prefix`.
+    pub fn build_projects(&self, project_prefix: &str) -> Result<Vec<Project>> {
+        let mut orders = KindOrders::default();
+        let mut projects = Vec::with_capacity(self.projects.len());
+
+        for (index, entry) in self.projects.iter().enumerate() {
+            let position = index + 1;
+
+            let Ok(tech) = entry.tech.parse::<Tech>() else {
+                bail!("Project {position} has an unknown tech: {}", entry.tech);
+            };
+
+            let kind = tech.kind();
+            if let Some(declared) = entry.kind {
+                if declared != kind {
+                    bail!(
+                        "Project {position} is declared as {declared}, but {tech} is a {kind} tech"
+                    );
+                }
+            }
+
+            let Ok(package_manager) = PackageManager::from_str(&entry.package_manager, true) else {
+                bail!(
+                    "Project {position} has an unknown package manager: {}",
+                    entry.package_manager
+                );
+            };
+            if !tech.get_package_managers().contains(&package_manager) {
+                bail!(
+                    "Project {position} uses {tech}, which can't be created with {package_manager}"
+                );
+            }
+
+            let order = orders.next(kind);
+            let name = entry
+                .name
+                .clone()
+                .unwrap_or_else(|| kind.default_name(project_prefix, order));
+            if let Some(other) = projects
+                .iter()
+                .position(|project: &Project| project.name == name)
+            {
+                bail!(
+                    "Project {position} is named {name}, which project {} already uses",
+                    other + 1
+                );
+            }
+
+            projects.push(
+                ProjectBuilder::default()
+                    .name(name)
+                    .template(entry.template.clone())
+                    .tech(tech)
+                    .init_git(entry.init_git)
+                    .should_install(entry.install)
+                    .package_manager(package_manager)
+                    .build()?,
+            );
+        }
+
+        Ok(projects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    fn manifest(projects: &str) -> Manifest {
+        toml::from_str(&format!("prefix = \"acme\"\n{projects}")).unwrap()
+    }
+
+    fn error(projects: &str) -> String {
+        let err = manifest(projects).build_projects("acme").unwrap_err();
+        format!("{err:#}")
+    }
+
+    #[test]
+    fn names_projects_after_the_prefix() {
+        let projects = manifest(
+            r#"
+            [[projects]]
+            tech = "react"
+            package-manager = "pnpm"
+
+            [[projects]]
+            tech = "vue"
+            package-manager = "npm"
+
+            [[projects]]
+            name = "backend"
+            tech = "fastapi"
+            package-manager = "uv"
+            "#,
+        )
+        .build_projects("acme")
+        .unwrap();
+
+        let names: Vec<_> = projects
+            .iter()
+            .map(|project| project.name.as_str())
+            .collect();
+        assert_eq!(names, ["acme-web", "acme-web-2", "backend"]);
+        assert_eq!(projects[0].package_manager(), &PackageManager::Pnpm);
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        let cases = [
+            (
+                r#"
+                [[projects]]
+                tech = "cobol"
+                package-manager = "npm"
+                "#,
+                "Project 1 has an unknown tech: cobol",
+            ),
+            (
+                r#"
+                [[projects]]
+                kind = "mobile"
+                tech = "react"
+                package-manager = "npm"
+                "#,
+                "Project 1 is declared as app, but React is a web tech",
+            ),
+            (
+                r#"
+                [[projects]]
+                tech = "react"
+                package-manager = "npm"
+
+                [[projects]]
+                tech = "react"
+                package-manager = "gradle"
+                "#,
+                "Project 2 has an unknown package manager: gradle",
+            ),
+            (
+                r#"
+                [[projects]]
+                tech = "react"
+                package-manager = "cargo"
+                "#,
+                "Project 1 uses React, which can't be created with cargo",
+            ),
+        ];
+
+        for (projects, expected) in cases {
+            assert_eq!(error(projects), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let explicit = error(
+            r#"
+            [[projects]]
+            name = "web"
+            tech = "react"
+            package-manager = "npm"
+
+            [[projects]]
+            name = "web"
+            tech = "vue"
+            package-manager = "npm"
+            "#,
+        );
+        assert_eq!(
+            explicit,
+            "Project 2 is named web, which project 1 already uses"
+        );
+
+        // A default name can clash with an explicit one too
+        let defaulted = error(
+            r#"
+            [[projects]]
+            tech = "react"
+            package-manager = "npm"
+
+            [[projects]]
+            name = "acme-web"
+            tech = "vue"
+            package-manager = "npm"
+            "#,
+        );
+        assert_eq!(
+            defaulted,
+            "Project 2 is named acme-web, which project 1 already uses"
+        );
+    }
+
+    #[test]
+    fn saved_answers_load_back() {
+        let original = manifest(
+            r#"
+            [[projects]]
+            name = "site"
+            tech = "react"
+            package-manager = "pnpm"
+            template = "acme/react-starter"
+
+            [[projects]]
+            tech = "fastapi"
+            package-manager = "uv"
+            install = false
+            "#,
+        );
+        let projects = original.build_projects("acme").unwrap();
+        let saved = Manifest::from_projects("acme", &projects);
+
+        for extension in ["toml", "json"] {
+            let path =
+                env::temp_dir().join(format!("seedrs-manifest-{}.{extension}", process::id()));
+            saved.save(&path).unwrap();
+            let loaded = Manifest::load(&path);
+            fs::remove_file(&path).unwrap();
+
+            let loaded = loaded.unwrap();
+            assert_eq!(
+                serde_json::to_value(&loaded).unwrap(),
+                serde_json::to_value(&saved).unwrap()
+            );
+            let rebuilt = loaded.build_projects("acme").unwrap();
+            assert_eq!(
+                serde_json::to_value(Manifest::from_projects("acme", &rebuilt)).unwrap(),
+                serde_json::to_value(&saved).unwrap()
+            );
+        }
+
+        let server = &saved.projects[1];
+        assert_eq!(server.name.as_deref(), Some("acme-server"));
+        assert_eq!(server.template, None);
+        assert!(!server.install);
+        assert_eq!(
+            saved.projects[0].template.as_deref(),
+            Some("acme/react-starter")
+        );
+    }
+}
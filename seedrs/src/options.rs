@@ -1,8 +1,30 @@
+use clap::ValueEnum;
+use std::{num::NonZeroUsize, path::PathBuf};
+
 use crate::{PackageManager, Tech};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Options {
     pub techs: Vec<Tech>,
     pub package_manager: Option<PackageManager>,
     pub project_prefix: Option<String>,
+    pub names: Vec<String>,
+    pub templates: Vec<String>,
+    pub init_git: Option<bool>,
+    pub should_install: Option<bool>,
+    pub no_input: bool,
+    pub manifest: Option<PathBuf>,
+    pub save_answers: Option<PathBuf>,
+    pub dry_run: Option<OutputFormat>,
+    pub jobs: Option<NonZeroUsize>,
+    pub verbose: bool,
+    pub report: OutputFormat,
+    pub keep_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
 }
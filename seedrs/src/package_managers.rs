@@ -1,15 +1,24 @@
 use anyhow::{bail, Result};
 use clap::ValueEnum;
 use colored::{ColoredString, Colorize};
-use std::fmt;
+use serde::{Deserialize, Serialize};
+use std::{fmt, io, process::Command, sync::OnceLock};
 use which::which;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, ValueEnum, Default)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PackageManager {
     #[default]
     Npm,
     Pnpm,
     Yarn,
+    Bun,
+    Deno,
+    Cargo,
+    Go,
+    Uv,
+    Poetry,
+    Flutter,
 }
 
 impl fmt::Display for PackageManager {
@@ -18,6 +27,13 @@ impl fmt::Display for PackageManager {
             PackageManager::Pnpm => "pnpm",
             PackageManager::Npm => "npm",
             PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+            PackageManager::Deno => "deno",
+            PackageManager::Cargo => "cargo",
+            PackageManager::Go => "go",
+            PackageManager::Uv => "uv",
+            PackageManager::Poetry => "poetry",
+            PackageManager::Flutter => "flutter",
         };
 
         write!(f, "{str}")
@@ -30,9 +46,103 @@ impl PackageManager {
             PackageManager::Npm => String::from("npm"),
             PackageManager::Pnpm => String::from("pnpm"),
             PackageManager::Yarn => String::from("yarn"),
+            PackageManager::Bun => String::from("bun"),
+            PackageManager::Deno => String::from("deno"),
+            PackageManager::Cargo => String::from("cargo"),
+            PackageManager::Go => String::from("go"),
+            PackageManager::Uv => String::from("uv"),
+            PackageManager::Poetry => String::from("poetry"),
+            PackageManager::Flutter => String::from("flutter"),
         }
     }
 
+    /// Command that runs the `create-<package>` initializer, e.g. `npm create vite@latest`.
+    /// Arguments appended to it are forwarded to the initializer.
+    pub fn create_command(&self, package: &str) -> Command {
+        match self {
+            PackageManager::Pnpm | PackageManager::Yarn | PackageManager::Bun => {
+                let mut command = Command::new(self.executable_name());
+                command.args(["create", package]);
+                command
+            }
+            PackageManager::Deno => {
+                let mut command = Command::new("deno");
+                command.args(["run", "-A", &format!("npm:create-{package}")]);
+                command
+            }
+            _ => {
+                let mut command = Command::new("npm");
+                command.args(["create", package, "--"]);
+                command
+            }
+        }
+    }
+
+    /// Command that downloads and runs the binary of `package`, like `npx` does.
+    /// Arguments appended to it are forwarded to the binary.
+    pub fn exec_command(&self, package: &str) -> Command {
+        match self {
+            PackageManager::Pnpm | PackageManager::Yarn if self.has_dlx() => {
+                let mut command = Command::new(self.executable_name());
+                command.args(["dlx", package]);
+                command
+            }
+            PackageManager::Bun => {
+                let mut command = Command::new("bunx");
+                command.arg(package);
+                command
+            }
+            PackageManager::Deno => {
+                let mut command = Command::new("deno");
+                command.args(["run", "-A", &format!("npm:{package}")]);
+                command
+            }
+            _ => {
+                let mut command = Command::new("npm");
+                command.args(["exec", "--yes", "--", package]);
+                command
+            }
+        }
+    }
+
+    /// Yarn classic has no `dlx`, its binaries are run through npm instead.
+    fn has_dlx(&self) -> bool {
+        match self {
+            PackageManager::Yarn => yarn_major_version().is_some_and(|major| major >= 2),
+            _ => true,
+        }
+    }
+
+    /// Command that installs the dependencies of a project, run inside it.
+    pub fn install_command(&self) -> Command {
+        let mut command = Command::new(self.executable_name());
+        match self {
+            PackageManager::Npm
+            | PackageManager::Pnpm
+            | PackageManager::Yarn
+            | PackageManager::Bun
+            | PackageManager::Deno => {
+                command.arg("install");
+            }
+            PackageManager::Cargo => {
+                command.arg("fetch");
+            }
+            PackageManager::Go => {
+                command.args(["mod", "download"]);
+            }
+            PackageManager::Uv => {
+                command.arg("sync");
+            }
+            PackageManager::Poetry => {
+                command.arg("install");
+            }
+            PackageManager::Flutter => {
+                command.args(["pub", "get"]);
+            }
+        };
+        command
+    }
+
     pub fn check_if_availabe(&self) -> Result<&Self> {
         if which(self.executable_name()).is_err() {
             bail!("Package manager isn't available for use!");
@@ -41,11 +151,47 @@ impl PackageManager {
         Ok(self)
     }
 
+    /// Whether corepack, shipped with node, can provide this package manager.
+    pub fn is_corepack_managed(&self) -> bool {
+        matches!(self, PackageManager::Pnpm | PackageManager::Yarn)
+    }
+
+    pub fn enable_with_corepack(&self) -> Result<()> {
+        // Stdout only carries the report
+        let status = Command::new("corepack")
+            .arg("enable")
+            .arg(self.executable_name())
+            .stdout(io::stderr())
+            .status()?;
+        if !status.success() {
+            bail!("corepack failed to enable {self}, exited with {status}");
+        }
+
+        Ok(())
+    }
+
     pub fn colorize(&self) -> ColoredString {
         match self {
             PackageManager::Npm => format!("{self}").red().bold(),
             PackageManager::Pnpm => format!("{self}").yellow().bold(),
             PackageManager::Yarn => format!("{self}").blue().bold(),
+            PackageManager::Bun => format!("{self}").bright_white().bold(),
+            PackageManager::Deno => format!("{self}").bright_green().bold(),
+            PackageManager::Cargo => format!("{self}").bright_yellow().bold(),
+            PackageManager::Go => format!("{self}").bright_cyan().bold(),
+            PackageManager::Uv => format!("{self}").magenta().bold(),
+            PackageManager::Poetry => format!("{self}").bright_blue().bold(),
+            PackageManager::Flutter => format!("{self}").bright_blue().bold(),
         }
     }
 }
+
+/// Major version of the yarn on the PATH, `None` when it can't be run.
+fn yarn_major_version() -> Option<u32> {
+    static VERSION: OnceLock<Option<u32>> = OnceLock::new();
+    *VERSION.get_or_init(|| {
+        let output = Command::new("yarn").arg("--version").output().ok()?;
+        let version = String::from_utf8(output.stdout).ok()?;
+        version.trim().split('.').next()?.parse().ok()
+    })
+}
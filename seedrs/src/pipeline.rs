@@ -0,0 +1,176 @@
+use anyhow::{bail, Context, Result};
+use rayon::{prelude::*, ThreadPoolBuilder};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, thread, time::Instant};
+
+use crate::{
+    cleanup,
+    logs::{self, StepLog},
+    progress::Progress,
+    projects::Project,
+    report::{ProjectReport, Report, StepStatus},
+    state::StateFile,
+};
+
+/// Lines of a failed step log shown along with the error.
+const OUTPUT_TAIL_LINES: usize = 10;
+
+/// One stage of a project creation, always run in the order they're declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Step {
+    Init,
+    PostInit,
+    GitInit,
+    Install,
+    InitialCommit,
+}
+
+impl Step {
+    /// Identifier used in file names.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Step::Init => "init",
+            Step::PostInit => "post-init",
+            Step::GitInit => "git-init",
+            Step::Install => "install",
+            Step::InitialCommit => "initial-commit",
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Step::Init => "init",
+            Step::PostInit => "post-init",
+            Step::GitInit => "git init",
+            Step::Install => "install dependencies",
+            Step::InitialCommit => "initial commit",
+        };
+
+        write!(f, "{str}")
+    }
+}
+
+fn run_step(
+    project: &Project,
+    project_prefix: &str,
+    step: Step,
+    label: Option<&str>,
+) -> Result<()> {
+    let log = StepLog::create(project_prefix, &project.name, step)?;
+
+    for mut command in project.step_commands(step, project_prefix)? {
+        let status = log
+            .run(&mut command, label)
+            .with_context(|| format!("Failed to spawn {step} for {}", project.name))?;
+        if !status.success() {
+            bail!(
+                "{step} for {} exited with {status}, full output in {}\n{}",
+                project.name,
+                log.path().display(),
+                log.tail(OUTPUT_TAIL_LINES)
+            );
+        }
+    }
+
+    project.finish_step(step, project_prefix)
+}
+
+/// Runs every step of `project` not completed yet in `state`, stopping at the
+/// first one that fails. The output of the commands goes to the step logs so
+/// concurrent projects don't interleave, and is only printed when `verbose`.
+pub fn run_project(
+    project: &Project,
+    project_prefix: &str,
+    state: &StateFile,
+    verbose: bool,
+    progress: &Progress,
+    index: usize,
+) -> ProjectReport {
+    let label = verbose.then_some(project.name.as_str());
+    let mut report = ProjectReport::new(project);
+    let mut steps = project.steps().into_iter();
+
+    for step in steps.by_ref() {
+        if state.is_completed(&project.name, step) {
+            report.record(step, StepStatus::Succeeded, None);
+            continue;
+        }
+        if cleanup::interrupted() {
+            report.record(step, StepStatus::Skipped, None);
+            report.error = Some(format!("{} was interrupted", project.name));
+            break;
+        }
+
+        progress.start(index, step);
+        let started = Instant::now();
+
+        let result = run_step(project, project_prefix, step, label)
+            .and_then(|()| state.complete(&project.name, step));
+        match result {
+            Ok(()) => report.record(step, StepStatus::Succeeded, Some(started.elapsed())),
+            Err(err) => {
+                report.record(step, StepStatus::Failed, Some(started.elapsed()));
+                report.error = Some(format!("{err:#}"));
+                break;
+            }
+        }
+    }
+    for step in steps {
+        report.record(step, StepStatus::Skipped, None);
+    }
+
+    report
+}
+
+/// Runs the pipeline of every project with at most `jobs` of them at the same
+/// time, reporting how each of their steps went.
+pub fn run(
+    project_prefix: &str,
+    projects: &[Project],
+    state: &StateFile,
+    jobs: usize,
+    verbose: bool,
+) -> Result<Report> {
+    let logs_dir = logs::logs_dir(project_prefix);
+    fs::create_dir_all(&logs_dir)
+        .with_context(|| format!("Failed to create {}", logs_dir.display()))?;
+
+    let pool = ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    // Streamed output would be overwritten by the live view
+    let progress = Progress::new(
+        projects.iter().map(|project| project.name.as_str()),
+        !verbose,
+    );
+
+    let reports: Vec<ProjectReport> = thread::scope(|scope| {
+        scope.spawn(|| progress.render());
+
+        let reports = pool.install(|| {
+            projects
+                .par_iter()
+                .enumerate()
+                .map(|(index, project)| {
+                    let report =
+                        run_project(project, project_prefix, state, verbose, &progress, index);
+                    if report.succeeded() {
+                        progress.succeed(index);
+                    } else {
+                        progress.fail(index);
+                    }
+                    report
+                })
+                .collect()
+        });
+
+        progress.finish();
+        reports
+    });
+
+    Ok(Report {
+        prefix: project_prefix.to_owned(),
+        projects: reports,
+    })
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt, process::Command};
+
+use crate::{logs, pipeline::Step, projects::Project, OutputFormat};
+
+/// Everything seedrs would do to create a workspace, built without touching disk.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Plan {
+    pub prefix: String,
+    pub actions: Vec<Action>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum Action {
+    CreateDirectory {
+        path: String,
+    },
+    #[serde(rename_all = "kebab-case")]
+    RunCommand {
+        project: String,
+        step: Step,
+        program: String,
+        args: Vec<String>,
+        cwd: Option<String>,
+        env: BTreeMap<String, String>,
+    },
+}
+
+impl Action {
+    fn from_command(project: &Project, step: Step, command: &Command) -> Self {
+        Self::RunCommand {
+            project: project.name.clone(),
+            step,
+            program: command.get_program().to_string_lossy().into_owned(),
+            args: command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            cwd: command
+                .get_current_dir()
+                .map(|dir| dir.to_string_lossy().into_owned()),
+            env: command
+                .get_envs()
+                .filter_map(|(key, value)| {
+                    Some((
+                        key.to_string_lossy().into_owned(),
+                        value?.to_string_lossy().into_owned(),
+                    ))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::CreateDirectory { path } => write!(f, "create directory {}", path.bold()),
+            Action::RunCommand {
+                project,
+                step,
+                program,
+                args,
+                cwd,
+                env,
+            } => {
+                write!(
+                    f,
+                    "[{}] {step}: {} {}",
+                    project.cyan(),
+                    program.bold(),
+                    args.join(" ")
+                )?;
+                if let Some(cwd) = cwd {
+                    write!(f, "\n     cwd: {cwd}")?;
+                }
+                for (key, value) in env {
+                    write!(f, "\n     env: {key}={value}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Plan {
+    pub fn build(project_prefix: &str, projects: &[Project]) -> Result<Self> {
+        let mut actions = vec![
+            Action::CreateDirectory {
+                path: project_prefix.to_owned(),
+            },
+            Action::CreateDirectory {
+                path: logs::logs_dir(project_prefix)
+                    .to_string_lossy()
+                    .into_owned(),
+            },
+        ];
+
+        for project in projects {
+            for step in project.steps() {
+                for command in project.step_commands(step, project_prefix)? {
+                    actions.push(Action::from_command(project, step, &command));
+                }
+            }
+        }
+
+        Ok(Self {
+            prefix: project_prefix.to_owned(),
+            actions,
+        })
+    }
+
+    pub fn print(&self, format: &OutputFormat) -> Result<()> {
+        match format {
+            OutputFormat::Human => {
+                println!("Execution plan for {}:", self.prefix.green().bold());
+                for (index, action) in self.actions.iter().enumerate() {
+                    println!("{:>3}. {action}", index + 1);
+                }
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(self)?),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use colored::Colorize;
+use std::{collections::BTreeMap, fmt, process::Command};
+use which::which;
+
+use crate::{projects::Project, PackageManager};
+
+const MIN_NODE_VERSION: [u32; 3] = [18, 0, 0];
+
+/// Executables that run on top of node, requiring it to be installed as well.
+const NODE_EXECUTABLES: &[&str] = &["npm", "npx", "pnpm", "yarn"];
+
+#[derive(Debug)]
+pub enum Status {
+    Available,
+    Missing,
+    Outdated { found: String },
+    Unknown { reason: String },
+}
+
+/// Result of looking up one executable, along with why seedrs needs it.
+#[derive(Debug)]
+pub struct Check {
+    pub executable: String,
+    pub reasons: Vec<String>,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn run(executable: &str, reasons: Vec<String>) -> Self {
+        let status = if which(executable).is_err() {
+            Status::Missing
+        } else if executable == "node" {
+            check_node_version()
+        } else {
+            Status::Available
+        };
+
+        Self {
+            executable: executable.to_owned(),
+            reasons,
+            status,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Available)
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.status {
+            Status::Available => write!(f, "{} {}", "✓".green().bold(), self.executable)?,
+            Status::Missing => write!(
+                f,
+                "{} {} isn't installed",
+                "✗".red().bold(),
+                self.executable.bold()
+            )?,
+            Status::Outdated { found } => write!(
+                f,
+                "{} {} {found} is older than the minimum {}",
+                "✗".red().bold(),
+                self.executable.bold(),
+                MIN_NODE_VERSION.map(|part| part.to_string()).join(".")
+            )?,
+            Status::Unknown { reason } => write!(
+                f,
+                "{} {} couldn't be checked: {reason}",
+                "✗".red().bold(),
+                self.executable.bold()
+            )?,
+        }
+
+        if !self.reasons.is_empty() {
+            write!(f, " (needed for {})", self.reasons.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn check_node_version() -> Status {
+    let output = match Command::new("node").arg("--version").output() {
+        Ok(output) => output,
+        Err(err) => {
+            return Status::Unknown {
+                reason: err.to_string(),
+            }
+        }
+    };
+
+    let found = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+    let version: Vec<u32> = found
+        .trim_start_matches('v')
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect();
+
+    if version.len() != 3 {
+        return Status::Unknown {
+            reason: format!("unexpected version {found}"),
+        };
+    }
+    if version.as_slice() < MIN_NODE_VERSION.as_slice() {
+        return Status::Outdated { found };
+    }
+    Status::Available
+}
+
+/// Every executable the steps of `projects` will spawn, mapped to the steps needing it.
+pub fn requirements(
+    project_prefix: &str,
+    projects: &[Project],
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut requirements: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut require = |command: &Command, reason: String| {
+        let program = command.get_program().to_string_lossy().into_owned();
+        if NODE_EXECUTABLES.contains(&program.as_str()) {
+            requirements
+                .entry(String::from("node"))
+                .or_default()
+                .push(reason.clone());
+        }
+        requirements.entry(program).or_default().push(reason);
+    };
+
+    for project in projects {
+        let name = &project.name;
+
+        if project.template().is_some() {
+            // Templates are always cloned through ssh
+            require(&Command::new("ssh"), format!("{name} template"));
+        }
+        for step in project.steps() {
+            for command in project.step_commands(step, project_prefix)? {
+                require(&command, format!("{name} {step}"));
+            }
+        }
+    }
+
+    Ok(requirements)
+}
+
+/// Checks every executable needed by `projects`, reporting all the problems at once.
+pub fn preflight(project_prefix: &str, projects: &[Project]) -> Result<()> {
+    let problems: Vec<Check> = requirements(project_prefix, projects)?
+        .into_iter()
+        .map(|(executable, reasons)| Check::run(&executable, reasons))
+        .filter(|check| !check.is_ok())
+        .collect();
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!("Some tools required to create {project_prefix} aren't available:");
+    for problem in &problems {
+        eprintln!("  {problem}");
+    }
+    bail!("Preflight checks failed, nothing was created")
+}
+
+/// Reports the availability of every tool seedrs may use.
+pub fn doctor() -> Result<()> {
+    let core = ["git", "node", "ssh"].map(|executable| Check::run(executable, vec![]));
+    let package_managers: Vec<Check> = PackageManager::value_variants()
+        .iter()
+        .map(|package_manager| Check::run(&package_manager.executable_name(), vec![]))
+        .collect();
+
+    println!("{}", "Core tools".bold());
+    for check in &core {
+        println!("  {check}");
+    }
+    println!("{}", "Package managers".bold());
+    for check in &package_managers {
+        println!("  {check}");
+    }
+
+    if core.iter().any(|check| !check.is_ok()) {
+        bail!("Some core tools aren't available");
+    }
+    Ok(())
+}
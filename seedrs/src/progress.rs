@@ -0,0 +1,170 @@
+use colored::Colorize;
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::pipeline::Step;
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Pending,
+    Running { step: Step },
+    Succeeded,
+    Failed { step: Step },
+}
+
+#[derive(Debug)]
+struct Row {
+    name: String,
+    state: State,
+    started: Option<Instant>,
+    finished: Option<Instant>,
+}
+
+impl Row {
+    fn elapsed(&self) -> Option<Duration> {
+        let started = self.started?;
+        Some(self.finished.unwrap_or_else(Instant::now) - started)
+    }
+}
+
+/// Status of every project being created, written to stderr. When `live` and on
+/// a terminal it's redrawn in place, otherwise each change is printed as a
+/// single line.
+#[derive(Debug)]
+pub struct Progress {
+    rows: Mutex<Vec<Row>>,
+    done: AtomicBool,
+    live: bool,
+    width: usize,
+}
+
+impl Progress {
+    pub fn new<'a>(names: impl Iterator<Item = &'a str>, live: bool) -> Self {
+        let rows: Vec<Row> = names
+            .map(|name| Row {
+                name: name.to_owned(),
+                state: State::Pending,
+                started: None,
+                finished: None,
+            })
+            .collect();
+        let width = rows.iter().map(|row| row.name.len()).max().unwrap_or(0);
+
+        Self {
+            rows: Mutex::new(rows),
+            done: AtomicBool::new(false),
+            live: live && io::stderr().is_terminal(),
+            width,
+        }
+    }
+
+    pub fn start(&self, index: usize, step: Step) {
+        self.update(index, |row| {
+            row.started.get_or_insert_with(Instant::now);
+            row.state = State::Running { step };
+        });
+    }
+
+    pub fn succeed(&self, index: usize) {
+        self.update(index, |row| {
+            row.finished = Some(Instant::now());
+            row.state = State::Succeeded;
+        });
+    }
+
+    pub fn fail(&self, index: usize) {
+        self.update(index, |row| {
+            row.finished = Some(Instant::now());
+            if let State::Running { step } = row.state {
+                row.state = State::Failed { step };
+            }
+        });
+    }
+
+    fn update(&self, index: usize, change: impl FnOnce(&mut Row)) {
+        let mut rows = self.rows.lock().expect("progress lock poisoned");
+        let row = &mut rows[index];
+        change(row);
+        if !self.live {
+            eprintln!("{}", self.line(row));
+        }
+    }
+
+    /// Redraws the progress until [`Progress::finish`] is called.
+    pub fn render(&self) {
+        if !self.live {
+            return;
+        }
+
+        let mut drawn = 0;
+        loop {
+            let finished = self.done.load(Ordering::Acquire);
+            drawn = self.draw(drawn);
+            if finished {
+                break;
+            }
+            thread::sleep(REFRESH_INTERVAL);
+        }
+    }
+
+    pub fn finish(&self) {
+        self.done.store(true, Ordering::Release);
+    }
+
+    fn draw(&self, previous: usize) -> usize {
+        let rows = self.rows.lock().expect("progress lock poisoned");
+        let mut stderr = io::stderr().lock();
+
+        if previous > 0 {
+            let _ = write!(stderr, "\x1b[{previous}A");
+        }
+        for row in rows.iter() {
+            let _ = writeln!(stderr, "\x1b[2K{}", self.line(row));
+        }
+        let _ = stderr.flush();
+        rows.len()
+    }
+
+    fn line(&self, row: &Row) -> String {
+        let name = format!("{:width$}", row.name, width = self.width);
+        let elapsed = row
+            .elapsed()
+            .map(|elapsed| format!("{:.1}s", elapsed.as_secs_f32()))
+            .unwrap_or_default();
+
+        match row.state {
+            State::Pending => format!("  {} {} {}", "·".dimmed(), name, "pending".dimmed()),
+            State::Running { step } => {
+                format!(
+                    "  {} {} {step} {}",
+                    "›".cyan().bold(),
+                    name,
+                    elapsed.dimmed()
+                )
+            }
+            State::Succeeded => format!(
+                "  {} {} {} {}",
+                "✓".green().bold(),
+                name,
+                "succeeded".green(),
+                elapsed.dimmed()
+            ),
+            State::Failed { step } => format!(
+                "  {} {} {} {}",
+                "✗".red().bold(),
+                name,
+                format!("failed at {step}").red(),
+                elapsed.dimmed()
+            ),
+        }
+    }
+}
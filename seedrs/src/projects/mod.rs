@@ -1,13 +1,16 @@
 use anyhow::Result;
-use degit::degit;
+use degit::Degit;
 use derive_builder::Builder;
-use std::process::{Child, Command};
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+};
 
-use crate::{PackageManager, Tech};
+use crate::{pipeline::Step, PackageManager, Tech};
 
 mod project_kind;
 
-pub use project_kind::ProjectKind;
+pub use project_kind::{KindOrders, ProjectKind};
 
 #[derive(Debug, Builder)]
 pub struct Project {
@@ -21,75 +24,276 @@ pub struct Project {
 }
 
 impl Project {
-    pub fn init_command_args(&self) -> Vec<String> {
-        let mut args = vec![];
-
-        match self.tech {
-            Tech::React => {
-                args.push("create".into());
-                args.push("vite@latest".into());
-                args.push(self.name.clone());
-                args.push("--".into());
-                args.push("--template".into());
-                args.push("react-ts".into());
-            }
-            Tech::NodeNest => {
-                args.push("exec".into());
-                args.push("--yes".into());
-                args.push("@nestjs/cli".into());
-                args.push("new".into());
-                args.push(self.name.clone());
-                args.push("--".into());
-                args.push("--skip-install".into());
-                args.push("-p".into());
-                args.push(self.package_manager.to_string());
-            }
-            Tech::ReactNative => {
-                args.push("create".into());
-                args.push("expo-app".into());
-                args.push(self.name.clone());
-                args.push("--".into());
-                args.push("--no-install".into());
-                args.push("--template".into());
-                args.push("blank-typescript".into());
-            }
-            Tech::Invalid => unreachable!(),
+    pub fn tech(&self) -> &Tech {
+        &self.tech
+    }
+
+    pub fn package_manager(&self) -> &PackageManager {
+        &self.package_manager
+    }
+
+    pub fn template(&self) -> Option<&str> {
+        self.template.as_deref()
+    }
+
+    /// Degit of the template into the project directory, when there's one.
+    pub fn degit(&self, project_prefix: &str) -> Result<Option<Degit>> {
+        let Some(template) = &self.template else {
+            return Ok(None);
         };
 
-        args
+        let opts = degit::options::Options {
+            url: template.clone(),
+            dest: Some(format!("{}/{}", project_prefix, &self.name)),
+            ssh: true,
+            ..Default::default()
+        };
+        Ok(Some(Degit::new(&opts)?))
     }
 
-    pub fn spawn_init_command(&self, project_prefix: &str) -> Result<Child> {
-        if let Some(template) = &self.template {
-            let destination = format!("{}/{}", project_prefix, &self.name);
-            return degit(template.clone(), Some(destination), true);
+    pub fn init_commands(&self, project_prefix: &str) -> Result<Vec<Command>> {
+        if let Some(degit) = self.degit(project_prefix)? {
+            return Ok(degit.commands());
         }
 
-        let project_creation_args = self.init_command_args();
-
-        let mut init = Command::new("npm");
+        let mut init = self.tech.init_command(&self.name, &self.package_manager);
+        init.current_dir(project_prefix)
+            .envs(self.tech.env(&self.name, &self.package_manager));
+        Ok(vec![init])
+    }
 
-        if self.tech.is_mobile() {
-            init.env("npm_config_user_agent", self.package_manager.to_string());
+    /// Commands the tech needs after its init command, templates don't run them.
+    pub fn post_init_commands(&self, project_prefix: &str) -> Vec<Command> {
+        if self.template.is_some() {
+            return vec![];
         }
 
-        Ok(init
-            .current_dir(project_prefix)
-            .args(project_creation_args)
-            .spawn()?)
+        let mut commands = self
+            .tech
+            .post_init_commands(&self.name, &self.package_manager);
+        for command in commands.iter_mut() {
+            command
+                .current_dir(format!("{project_prefix}/{}", &self.name))
+                .envs(self.tech.env(&self.name, &self.package_manager));
+        }
+        commands
     }
 
-    pub fn spawn_git_init_command(&self, project_prefix: &str) -> Result<Child> {
-        Ok(Command::new("git")
+    pub fn git_init_command(&self, project_prefix: &str) -> Command {
+        let mut command = Command::new("git");
+        command
             .current_dir(format!("{project_prefix}/{}", &self.name))
-            .arg("init")
-            .spawn()?)
+            .arg("init");
+        command
     }
 
-    pub fn spawn_install_deps_command(&self, project_prefix: &str) -> Result<Child> {
-        Ok(Command::new(self.package_manager.to_string())
-            .current_dir(format!("{project_prefix}/{}", &self.name))
-            .arg("install")
-            .spawn()?)
+    pub fn install_deps_command(&self, project_prefix: &str) -> Command {
+        let mut command = self.package_manager.install_command();
+        command.current_dir(format!("{project_prefix}/{}", &self.name));
+        command
+    }
+
+    pub fn initial_commit_commands(&self, project_prefix: &str) -> Vec<Command> {
+        let project_dir = format!("{project_prefix}/{}", &self.name);
+
+        let mut add = Command::new("git");
+        add.current_dir(&project_dir).args(["add", "--all"]);
+        let mut commit = Command::new("git");
+        commit.current_dir(&project_dir);
+        // Fresh machines and CI runners often have no identity, which would fail
+        // an otherwise scaffolded project
+        for (key, fallback) in [("user.name", "seedrs"), ("user.email", "seedrs@localhost")] {
+            if !git_config_is_set(&project_dir, key) {
+                commit.args(["-c", &format!("{key}={fallback}")]);
+            }
+        }
+        // Techs that only create the directory leave nothing to commit
+        commit.args([
+            "commit",
+            "--quiet",
+            "--allow-empty",
+            "--message",
+            "Initial commit",
+        ]);
+
+        vec![add, commit]
+    }
+
+    /// Steps needed to create this project, in the order they must run.
+    pub fn steps(&self) -> Vec<Step> {
+        let mut steps = vec![Step::Init];
+        if self.template.is_none()
+            && !self
+                .tech
+                .post_init_commands(&self.name, &self.package_manager)
+                .is_empty()
+        {
+            steps.push(Step::PostInit);
+        }
+        if self.init_git {
+            steps.push(Step::GitInit);
+        }
+        if self.should_install {
+            steps.push(Step::Install);
+        }
+        if self.init_git {
+            steps.push(Step::InitialCommit);
+        }
+        steps
+    }
+
+    pub fn step_commands(&self, step: Step, project_prefix: &str) -> Result<Vec<Command>> {
+        Ok(match step {
+            Step::Init => self.init_commands(project_prefix)?,
+            Step::PostInit => self.post_init_commands(project_prefix),
+            Step::GitInit => vec![self.git_init_command(project_prefix)],
+            Step::Install => vec![self.install_deps_command(project_prefix)],
+            Step::InitialCommit => self.initial_commit_commands(project_prefix),
+        })
+    }
+
+    /// Work done by seedrs itself once every command of `step` succeeded.
+    pub fn finish_step(&self, step: Step, project_prefix: &str) -> Result<()> {
+        match (step, self.degit(project_prefix)?) {
+            (Step::Init, Some(degit)) => degit.finish(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Whether git resolves `key` for the repository at `dir`, or globally when it
+/// doesn't exist yet.
+fn git_config_is_set(dir: &str, key: &str) -> bool {
+    let mut command = Command::new("git");
+    if Path::new(dir).is_dir() {
+        command.current_dir(dir);
+    }
+    command
+        .args(["config", "--get", key])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProjectKind, TechDefinition};
+
+    #[derive(Debug)]
+    struct TestTech {
+        post_init: bool,
+    }
+
+    impl TechDefinition for TestTech {
+        fn id(&self) -> &str {
+            "test"
+        }
+
+        fn name(&self) -> &str {
+            "Test"
+        }
+
+        fn kind(&self) -> ProjectKind {
+            ProjectKind::Api
+        }
+
+        fn package_managers(&self) -> Vec<PackageManager> {
+            vec![PackageManager::Npm]
+        }
+
+        fn init_command(&self, name: &str, _package_manager: &PackageManager) -> Command {
+            let mut command = Command::new("mkdir");
+            command.arg(name);
+            command
+        }
+
+        fn post_init_commands(
+            &self,
+            _name: &str,
+            _package_manager: &PackageManager,
+        ) -> Vec<Command> {
+            if self.post_init {
+                vec![Command::new("true")]
+            } else {
+                vec![]
+            }
+        }
+    }
+
+    fn project(init_git: bool, should_install: bool, post_init: bool, template: bool) -> Project {
+        ProjectBuilder::default()
+            .name(String::from("api"))
+            .tech(Tech::new(TestTech { post_init }))
+            .package_manager(PackageManager::Npm)
+            .init_git(init_git)
+            .should_install(should_install)
+            .template(template.then(|| String::from("user/repo")))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn steps_follow_git_and_install_choices() {
+        use Step::*;
+
+        let cases = [
+            (false, false, vec![Init]),
+            (false, true, vec![Init, Install]),
+            (true, false, vec![Init, GitInit, InitialCommit]),
+            (true, true, vec![Init, GitInit, Install, InitialCommit]),
+        ];
+        for (init_git, should_install, expected) in cases {
+            assert_eq!(
+                project(init_git, should_install, false, false).steps(),
+                expected,
+                "init_git: {init_git}, should_install: {should_install}"
+            );
+        }
+    }
+
+    #[test]
+    fn post_init_runs_right_after_init() {
+        use Step::*;
+
+        let cases = [
+            (false, false, vec![Init, PostInit]),
+            (false, true, vec![Init, PostInit, Install]),
+            (true, false, vec![Init, PostInit, GitInit, InitialCommit]),
+            (
+                true,
+                true,
+                vec![Init, PostInit, GitInit, Install, InitialCommit],
+            ),
+        ];
+        for (init_git, should_install, expected) in cases {
+            assert_eq!(
+                project(init_git, should_install, true, false).steps(),
+                expected,
+                "init_git: {init_git}, should_install: {should_install}"
+            );
+        }
+    }
+
+    #[test]
+    fn templates_skip_post_init() {
+        use Step::*;
+
+        let cases = [
+            (false, false, vec![Init]),
+            (false, true, vec![Init, Install]),
+            (true, false, vec![Init, GitInit, InitialCommit]),
+            (true, true, vec![Init, GitInit, Install, InitialCommit]),
+        ];
+        for (init_git, should_install, expected) in cases {
+            for post_init in [false, true] {
+                assert_eq!(
+                    project(init_git, should_install, post_init, true).steps(),
+                    expected,
+                    "init_git: {init_git}, should_install: {should_install}, post_init: {post_init}"
+                );
+            }
+        }
     }
 }
@@ -1,14 +1,32 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-use crate::Tech;
+use crate::{techs::registry, Tech};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
 pub enum ProjectKind {
     Web,
+    #[serde(alias = "app")]
+    #[value(alias = "app")]
     Mobile,
     Api,
 }
 
+/// Keeps track of how many projects of each kind were named, so default names
+/// follow the same `<prefix>-<suffix>-<order>` scheme of the prompts.
+#[derive(Debug, Default)]
+pub struct KindOrders([i32; 3]);
+
+impl KindOrders {
+    pub fn next(&mut self, kind: ProjectKind) -> i32 {
+        let order = &mut self.0[kind as usize];
+        *order += 1;
+        *order
+    }
+}
+
 impl ProjectKind {
     pub fn values() -> Vec<Self> {
         vec![Self::Web, Self::Mobile, Self::Api]
@@ -22,13 +40,17 @@ impl ProjectKind {
         }
     }
 
-    pub fn get_techs(&self) -> Vec<Tech> {
-        match self {
-            ProjectKind::Web => vec![Tech::React],
-            ProjectKind::Mobile => vec![Tech::ReactNative],
-            ProjectKind::Api => vec![Tech::NodeNest],
+    pub fn default_name(&self, prefix: &str, order: i32) -> String {
+        if order == 1 {
+            format!("{}-{}", prefix, self.get_suffix())
+        } else {
+            format!("{}-{}-{}", prefix, self.get_suffix(), order)
         }
     }
+
+    pub fn get_techs(&self) -> Vec<Tech> {
+        registry().of_kind(*self)
+    }
 }
 
 impl Display for ProjectKind {
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::{error::Error, fmt, path::Path, time::Duration};
+
+use crate::{pipeline::Step, projects::Project, OutputFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StepStatus {
+    Succeeded,
+    Failed,
+    /// Never ran because an earlier step failed.
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StepReport {
+    pub step: Step,
+    pub status: StepStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u128>,
+}
+
+impl fmt::Display for StepReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let duration = self
+            .duration_ms
+            .map(|ms| format!(" {:.1}s", ms as f64 / 1000.0))
+            .unwrap_or_default();
+
+        match self.status {
+            StepStatus::Succeeded => {
+                write!(f, "{} {}{}", "✓".green(), self.step, duration.dimmed())
+            }
+            StepStatus::Failed => write!(
+                f,
+                "{} {}{}",
+                "✗".red(),
+                self.step.to_string().red(),
+                duration.dimmed()
+            ),
+            StepStatus::Skipped => write!(f, "{}", format!("- {}", self.step).dimmed()),
+        }
+    }
+}
+
+/// Outcome of every step of a single project.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectReport {
+    pub name: String,
+    pub tech: String,
+    pub package_manager: String,
+    pub steps: Vec<StepReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProjectReport {
+    pub fn new(project: &Project) -> Self {
+        Self {
+            name: project.name.clone(),
+            tech: project.tech().id().to_owned(),
+            package_manager: project.package_manager().to_string(),
+            steps: vec![],
+            error: None,
+        }
+    }
+
+    pub fn record(&mut self, step: Step, status: StepStatus, duration: Option<Duration>) {
+        self.steps.push(StepReport {
+            step,
+            status,
+            duration_ms: duration.map(|duration| duration.as_millis()),
+        });
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Summary of a whole run, printed once every project is done.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Report {
+    pub prefix: String,
+    pub projects: Vec<ProjectReport>,
+}
+
+impl Report {
+    pub fn failures(&self) -> usize {
+        self.projects
+            .iter()
+            .filter(|project| !project.succeeded())
+            .count()
+    }
+
+    /// Points the errors at the logs once they were moved from `from` to `to`.
+    pub fn move_logs(&mut self, from: &Path, to: &Path) {
+        let (from, to) = (from.display().to_string(), to.display().to_string());
+        for error in self
+            .projects
+            .iter_mut()
+            .filter_map(|project| project.error.as_mut())
+        {
+            *error = error.replace(&from, &to);
+        }
+    }
+
+    pub fn print_errors(&self) {
+        for error in self
+            .projects
+            .iter()
+            .filter_map(|project| project.error.as_ref())
+        {
+            eprintln!("\nError: {error}");
+        }
+    }
+
+    pub fn print(&self, format: &OutputFormat) -> Result<()> {
+        match format {
+            OutputFormat::Human => self.print_table(),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(self)?),
+        }
+        Ok(())
+    }
+
+    fn print_table(&self) {
+        let headers = ["project", "tech", "package manager"];
+        let rows: Vec<[&str; 3]> = self
+            .projects
+            .iter()
+            .map(|project| {
+                [
+                    project.name.as_str(),
+                    project.tech.as_str(),
+                    project.package_manager.as_str(),
+                ]
+            })
+            .collect();
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].len())
+                    .chain([headers[column].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        println!("\nSummary for {}:", self.prefix.green().bold());
+        let header: Vec<String> = headers
+            .iter()
+            .zip(&widths)
+            .map(|(header, width)| format!("{header:width$}"))
+            .collect();
+        println!("  {}  {}", header.join("  ").bold(), "steps".bold());
+
+        for (row, project) in rows.iter().zip(&self.projects) {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect();
+            let steps: Vec<String> = project.steps.iter().map(StepReport::to_string).collect();
+            println!("  {}  {}", cells.join("  "), steps.join("  "));
+        }
+    }
+}
+
+/// Returned when some of the projects couldn't be created, the binary exits
+/// with [`PartialFailure::EXIT_CODE`] on it.
+#[derive(Debug)]
+pub struct PartialFailure {
+    pub failed: usize,
+    pub total: usize,
+}
+
+impl PartialFailure {
+    pub const EXIT_CODE: i32 = 2;
+}
+
+impl fmt::Display for PartialFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} projects failed", self.failed, self.total)
+    }
+}
+
+impl Error for PartialFailure {}
@@ -0,0 +1,150 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use crate::{pipeline::Step, projects::Project, Manifest, ManifestProject};
+
+/// File, relative to the project prefix, recording the progress of a run.
+pub const STATE_FILE: &str = ".seedrs/state.json";
+
+/// Every project of a workspace along with the steps they already completed,
+/// enough to resume a run that didn't finish.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct State {
+    pub manifest: Manifest,
+    #[serde(default)]
+    pub completed: BTreeMap<String, BTreeSet<Step>>,
+}
+
+impl State {
+    pub fn is_completed(&self, project: &str, step: Step) -> bool {
+        self.completed
+            .get(project)
+            .is_some_and(|steps| steps.contains(&step))
+    }
+}
+
+/// [`State`] kept in sync with its file as steps complete.
+#[derive(Debug)]
+pub struct StateFile {
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+impl StateFile {
+    pub fn path(project_prefix: &str) -> PathBuf {
+        Path::new(project_prefix).join(STATE_FILE)
+    }
+
+    pub fn create(project_prefix: &str, projects: &[Project]) -> Result<Self> {
+        let path = Self::path(project_prefix);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+
+        let file = Self {
+            path,
+            state: Mutex::new(State {
+                manifest: Manifest::from_projects(project_prefix, projects),
+                completed: BTreeMap::new(),
+            }),
+        };
+        file.save()?;
+        Ok(file)
+    }
+
+    pub fn load(project_prefix: &str) -> Result<Self> {
+        let path = Self::path(project_prefix);
+        let content = fs::read_to_string(&path).with_context(|| {
+            format!(
+                "No seedrs run found in {project_prefix}, can't read {}",
+                path.display()
+            )
+        })?;
+        let state = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid state file {}", path.display()))?;
+
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    fn update(&self, change: impl FnOnce(&mut State)) -> Result<()> {
+        let mut state = self.state.lock().expect("state lock poisoned");
+        change(&mut state);
+        self.write(&state)
+    }
+
+    fn save(&self) -> Result<()> {
+        self.write(&self.state.lock().expect("state lock poisoned"))
+    }
+
+    /// Writes a temporary file first and renames it over the state, so a run
+    /// dying mid-write can't leave it truncated.
+    fn write(&self, state: &State) -> Result<()> {
+        let temporary = self.path.with_extension("json.tmp");
+        fs::write(&temporary, serde_json::to_string_pretty(state)?)
+            .with_context(|| format!("Failed to write {}", temporary.display()))?;
+        fs::rename(&temporary, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn manifest(&self) -> Manifest {
+        self.state
+            .lock()
+            .expect("state lock poisoned")
+            .manifest
+            .clone()
+    }
+
+    pub fn is_completed(&self, project: &str, step: Step) -> bool {
+        self.state
+            .lock()
+            .expect("state lock poisoned")
+            .is_completed(project, step)
+    }
+
+    pub fn complete(&self, project: &str, step: Step) -> Result<()> {
+        self.update(|state| {
+            state
+                .completed
+                .entry(project.to_owned())
+                .or_default()
+                .insert(step);
+        })
+    }
+
+    pub fn add_projects(&self, projects: &[Project]) -> Result<()> {
+        self.update(|state| {
+            state
+                .manifest
+                .projects
+                .extend(projects.iter().map(ManifestProject::from));
+        })
+    }
+
+    /// Whether any project completed at least one step.
+    pub fn has_progress(&self) -> bool {
+        self.state
+            .lock()
+            .expect("state lock poisoned")
+            .completed
+            .values()
+            .any(|steps| !steps.is_empty())
+    }
+
+    /// Forgets the progress of `project`, which will start over from its first step.
+    pub fn reset(&self, project: &str) -> Result<()> {
+        self.update(|state| {
+            state.completed.remove(project);
+        })
+    }
+}
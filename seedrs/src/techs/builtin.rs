@@ -0,0 +1,478 @@
+use colored::Color;
+use std::process::Command;
+
+use super::TechDefinition;
+use crate::{PackageManager, ProjectKind};
+
+const JS_PACKAGE_MANAGERS: &[PackageManager] = &[
+    PackageManager::Npm,
+    PackageManager::Yarn,
+    PackageManager::Pnpm,
+    PackageManager::Bun,
+];
+
+type EnvFn = fn(&PackageManager) -> Vec<(String, String)>;
+type InitFn = fn(&str, &PackageManager) -> Command;
+type PostInitFn = fn(&str, &PackageManager) -> Vec<Command>;
+
+/// Tech shipped with seedrs, every built-in stack is declared in [`techs`].
+#[derive(Debug)]
+pub struct BuiltinTech {
+    id: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+    kind: ProjectKind,
+    package_managers: &'static [PackageManager],
+    color: Color,
+    env: Option<EnvFn>,
+    init: InitFn,
+    post_init: Option<PostInitFn>,
+}
+
+impl TechDefinition for BuiltinTech {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        self.aliases.to_vec()
+    }
+
+    fn kind(&self) -> ProjectKind {
+        self.kind
+    }
+
+    fn package_managers(&self) -> Vec<PackageManager> {
+        self.package_managers.to_vec()
+    }
+
+    fn init_command(&self, name: &str, package_manager: &PackageManager) -> Command {
+        (self.init)(name, package_manager)
+    }
+
+    fn env(&self, _name: &str, package_manager: &PackageManager) -> Vec<(String, String)> {
+        self.env.map(|env| env(package_manager)).unwrap_or_default()
+    }
+
+    fn post_init_commands(&self, name: &str, package_manager: &PackageManager) -> Vec<Command> {
+        self.post_init
+            .map(|post_init| post_init(name, package_manager))
+            .unwrap_or_default()
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+fn create<const N: usize>(
+    package_manager: &PackageManager,
+    package: &str,
+    args: [&str; N],
+) -> Command {
+    let mut command = package_manager.create_command(package);
+    command.args(args);
+    command
+}
+
+fn exec<const N: usize>(
+    package_manager: &PackageManager,
+    package: &str,
+    args: [&str; N],
+) -> Command {
+    let mut command = package_manager.exec_command(package);
+    command.args(args);
+    command
+}
+
+fn command<const N: usize>(program: &str, args: [&str; N]) -> Command {
+    let mut command = Command::new(program);
+    command.args(args);
+    command
+}
+
+/// Expo reads the package manager it should configure from the user agent.
+fn expo_env(package_manager: &PackageManager) -> Vec<(String, String)> {
+    vec![(
+        String::from("npm_config_user_agent"),
+        package_manager.to_string(),
+    )]
+}
+
+fn expo(name: &str, package_manager: &PackageManager, template: &str) -> Command {
+    create(
+        package_manager,
+        "expo-app",
+        [name, "--no-install", "--template", template],
+    )
+}
+
+/// `my-project-app` -> `MyProjectApp`, React-Native CLI only accepts alphanumeric names.
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// `my-project-app` -> `my_project_app`, Flutter requires valid Dart package names.
+fn snake_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+pub fn techs() -> Vec<BuiltinTech> {
+    vec![
+        BuiltinTech {
+            id: "react",
+            name: "React",
+            aliases: &[],
+            kind: ProjectKind::Web,
+            package_managers: &[
+                PackageManager::Npm,
+                PackageManager::Yarn,
+                PackageManager::Pnpm,
+                PackageManager::Bun,
+                PackageManager::Deno,
+            ],
+            color: Color::BrightBlue,
+            env: None,
+            init: |name, package_manager| {
+                create(
+                    package_manager,
+                    "vite@latest",
+                    [name, "--template", "react-ts"],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "next",
+            name: "Next.js",
+            aliases: &["nextjs", "next-js"],
+            kind: ProjectKind::Web,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::BrightWhite,
+            env: None,
+            init: |name, package_manager| {
+                create(
+                    package_manager,
+                    "next-app@latest",
+                    [
+                        name,
+                        "--yes",
+                        "--ts",
+                        "--skip-install",
+                        "--disable-git",
+                        &format!("--use-{package_manager}"),
+                    ],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "vue",
+            name: "Vue",
+            aliases: &["vuejs"],
+            kind: ProjectKind::Web,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Green,
+            env: None,
+            init: |name, package_manager| {
+                create(
+                    package_manager,
+                    "vue@latest",
+                    [name, "--ts", "--router", "--pinia", "--eslint"],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "svelte",
+            name: "SvelteKit",
+            aliases: &["sveltekit", "svelte-kit"],
+            kind: ProjectKind::Web,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Red,
+            env: None,
+            init: |name, package_manager| {
+                exec(
+                    package_manager,
+                    "sv@latest",
+                    [
+                        "create",
+                        name,
+                        "--template",
+                        "minimal",
+                        "--types",
+                        "ts",
+                        "--no-add-ons",
+                        "--no-install",
+                    ],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "angular",
+            name: "Angular",
+            aliases: &["ng"],
+            kind: ProjectKind::Web,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Magenta,
+            env: None,
+            init: |name, package_manager| {
+                exec(
+                    package_manager,
+                    "@angular/cli@latest",
+                    [
+                        "new",
+                        name,
+                        "--defaults",
+                        "--skip-git",
+                        "--skip-install",
+                        "--package-manager",
+                        &package_manager.to_string(),
+                    ],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "node-nest",
+            name: "NestJS",
+            aliases: &["nestjs", "node-nestjs"],
+            kind: ProjectKind::Api,
+            // The Nest CLI only knows how to set up npm, yarn and pnpm projects
+            package_managers: &[
+                PackageManager::Npm,
+                PackageManager::Yarn,
+                PackageManager::Pnpm,
+            ],
+            color: Color::BrightRed,
+            env: None,
+            init: |name, package_manager| {
+                exec(
+                    package_manager,
+                    "@nestjs/cli",
+                    [
+                        "new",
+                        name,
+                        "--skip-install",
+                        "-p",
+                        &package_manager.to_string(),
+                    ],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "express",
+            name: "Express",
+            aliases: &["node-express"],
+            kind: ProjectKind::Api,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::BrightBlack,
+            env: None,
+            init: |name, package_manager| {
+                exec(
+                    package_manager,
+                    "express-generator@latest",
+                    ["--no-view", name],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "fastify",
+            name: "Fastify",
+            aliases: &["node-fastify"],
+            kind: ProjectKind::Api,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::White,
+            env: None,
+            init: |name, package_manager| {
+                exec(
+                    package_manager,
+                    "fastify-cli@latest",
+                    ["generate", name, "--lang=ts"],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "rust-axum",
+            name: "Rust (Axum)",
+            aliases: &["axum"],
+            kind: ProjectKind::Api,
+            package_managers: &[PackageManager::Cargo],
+            color: Color::BrightYellow,
+            env: None,
+            init: |name, _| command("cargo", ["new", name, "--bin", "--vcs", "none"]),
+            post_init: Some(|_, _| {
+                vec![
+                    command("cargo", ["add", "axum"]),
+                    command("cargo", ["add", "tokio", "--features", "full"]),
+                ]
+            }),
+        },
+        BuiltinTech {
+            id: "go",
+            name: "Go (net/http)",
+            aliases: &["golang"],
+            kind: ProjectKind::Api,
+            package_managers: &[PackageManager::Go],
+            color: Color::BrightCyan,
+            env: None,
+            init: |name, _| {
+                command(
+                    "go",
+                    [
+                        "run",
+                        "golang.org/x/tools/cmd/gonew@latest",
+                        "golang.org/x/example/helloserver",
+                        name,
+                        name,
+                    ],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "fastapi",
+            name: "Python (FastAPI)",
+            aliases: &["python-fastapi"],
+            kind: ProjectKind::Api,
+            package_managers: &[PackageManager::Uv, PackageManager::Poetry],
+            color: Color::BrightGreen,
+            env: None,
+            init: |name, package_manager| match package_manager {
+                PackageManager::Poetry => command("poetry", ["new", name]),
+                _ => command("uv", ["init", name, "--app", "--vcs", "none"]),
+            },
+            post_init: Some(|_, package_manager| {
+                vec![command(
+                    &package_manager.executable_name(),
+                    ["add", "fastapi[standard]"],
+                )]
+            }),
+        },
+        BuiltinTech {
+            id: "react-native",
+            name: "Expo (blank-typescript)",
+            aliases: &["rn", "expo-blank-typescript"],
+            kind: ProjectKind::Mobile,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Cyan,
+            env: Some(expo_env),
+            init: |name, package_manager| expo(name, package_manager, "blank-typescript"),
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "expo",
+            name: "Expo (default)",
+            aliases: &["expo-default"],
+            kind: ProjectKind::Mobile,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Cyan,
+            env: Some(expo_env),
+            init: |name, package_manager| expo(name, package_manager, "default"),
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "expo-blank",
+            name: "Expo (blank)",
+            aliases: &[],
+            kind: ProjectKind::Mobile,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Cyan,
+            env: Some(expo_env),
+            init: |name, package_manager| expo(name, package_manager, "blank"),
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "expo-tabs",
+            name: "Expo (tabs)",
+            aliases: &[],
+            kind: ProjectKind::Mobile,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Cyan,
+            env: Some(expo_env),
+            init: |name, package_manager| expo(name, package_manager, "tabs"),
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "expo-bare",
+            name: "Expo (bare-minimum)",
+            aliases: &["expo-bare-minimum"],
+            kind: ProjectKind::Mobile,
+            package_managers: JS_PACKAGE_MANAGERS,
+            color: Color::Cyan,
+            env: Some(expo_env),
+            init: |name, package_manager| expo(name, package_manager, "bare-minimum"),
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "react-native-cli",
+            name: "React-Native CLI",
+            aliases: &["rn-cli"],
+            kind: ProjectKind::Mobile,
+            // The community CLI can't set up pnpm projects
+            package_managers: &[
+                PackageManager::Npm,
+                PackageManager::Yarn,
+                PackageManager::Bun,
+            ],
+            color: Color::BrightCyan,
+            env: None,
+            init: |name, package_manager| {
+                exec(
+                    package_manager,
+                    "@react-native-community/cli@latest",
+                    [
+                        "init",
+                        &pascal_case(name),
+                        "--directory",
+                        name,
+                        "--skip-install",
+                        "--skip-git-init",
+                        "--install-pods",
+                        "false",
+                        "--pm",
+                        &package_manager.to_string(),
+                    ],
+                )
+            },
+            post_init: None,
+        },
+        BuiltinTech {
+            id: "flutter",
+            name: "Flutter",
+            aliases: &[],
+            kind: ProjectKind::Mobile,
+            package_managers: &[PackageManager::Flutter],
+            color: Color::Blue,
+            env: None,
+            init: |name, _| {
+                command(
+                    "flutter",
+                    ["create", "--project-name", &snake_case(name), name],
+                )
+            },
+            post_init: None,
+        },
+    ]
+}
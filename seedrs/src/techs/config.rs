@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use colored::Color;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use super::TechDefinition;
+use crate::{PackageManager, ProjectKind};
+
+/// Tech declared by the user in `~/.config/seedrs/techs/<tech>.toml`.
+///
+/// `{name}` and `{pm}` are replaced by the project name and the chosen package
+/// manager in `command`, `args`, `env` and `post-init`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ConfigTech {
+    id: String,
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    kind: ProjectKind,
+    package_managers: Vec<PackageManager>,
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    post_init: Vec<Vec<String>>,
+    color: Option<String>,
+}
+
+fn fill(value: &str, name: &str, package_manager: &PackageManager) -> String {
+    value
+        .replace("{name}", name)
+        .replace("{pm}", &package_manager.to_string())
+}
+
+impl TechDefinition for ConfigTech {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        self.aliases.iter().map(String::as_str).collect()
+    }
+
+    fn kind(&self) -> ProjectKind {
+        self.kind
+    }
+
+    fn package_managers(&self) -> Vec<PackageManager> {
+        self.package_managers.clone()
+    }
+
+    fn init_command(&self, name: &str, package_manager: &PackageManager) -> Command {
+        let mut command = Command::new(fill(&self.command, name, package_manager));
+        command.args(self.args.iter().map(|arg| fill(arg, name, package_manager)));
+        command
+    }
+
+    fn env(&self, name: &str, package_manager: &PackageManager) -> Vec<(String, String)> {
+        self.env
+            .iter()
+            .map(|(key, value)| (key.clone(), fill(value, name, package_manager)))
+            .collect()
+    }
+
+    fn post_init_commands(&self, name: &str, package_manager: &PackageManager) -> Vec<Command> {
+        self.post_init
+            .iter()
+            .filter_map(|line| {
+                let (program, args) = line.split_first()?;
+                let mut command = Command::new(fill(program, name, package_manager));
+                command.args(args.iter().map(|arg| fill(arg, name, package_manager)));
+                Some(command)
+            })
+            .collect()
+    }
+
+    fn color(&self) -> Color {
+        self.color
+            .as_deref()
+            .and_then(|color| color.parse().ok())
+            .unwrap_or(Color::White)
+    }
+}
+
+/// Directory user techs are loaded from, `$XDG_CONFIG_HOME/seedrs/techs` or
+/// `~/.config/seedrs/techs`.
+pub fn techs_dir() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+    Some(config.join("seedrs").join("techs"))
+}
+
+pub fn load(path: &Path) -> Result<ConfigTech> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tech {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Invalid tech {}", path.display()))
+}
+
+/// Every `*.toml` file of `dir`, sorted so registration order is stable.
+pub fn tech_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut files = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,219 @@
+use anyhow::{bail, Result};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser, ValueParserFactory};
+use colored::{Color, ColoredString, Colorize};
+use core::fmt;
+use std::{
+    ops::Deref,
+    path::Path,
+    process::Command,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{PackageManager, ProjectKind};
+
+mod builtin;
+mod config;
+
+pub use config::techs_dir;
+
+/// Describes how a technology is scaffolded. Implement it and add it to a
+/// [`TechRegistry`] to make a new stack available to seedrs.
+pub trait TechDefinition: fmt::Debug + Send + Sync {
+    /// Identifier used on the command line and in manifests, e.g. `react`.
+    fn id(&self) -> &str;
+
+    /// Name shown to the user, e.g. `React`.
+    fn name(&self) -> &str;
+
+    /// Other identifiers accepted for this tech.
+    fn aliases(&self) -> Vec<&str> {
+        vec![]
+    }
+
+    fn kind(&self) -> ProjectKind;
+
+    fn package_managers(&self) -> Vec<PackageManager>;
+
+    /// Command that creates the project `name` in the current directory.
+    fn init_command(&self, name: &str, package_manager: &PackageManager) -> Command;
+
+    /// Environment variables set on the init and post-init commands of `name`.
+    fn env(&self, _name: &str, _package_manager: &PackageManager) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// Commands run inside the project directory after a successful init.
+    fn post_init_commands(&self, _name: &str, _package_manager: &PackageManager) -> Vec<Command> {
+        vec![]
+    }
+
+    fn color(&self) -> Color {
+        Color::White
+    }
+}
+
+/// Handle to a registered [`TechDefinition`].
+#[derive(Clone)]
+pub struct Tech(Arc<dyn TechDefinition>);
+
+impl Tech {
+    pub fn new(definition: impl TechDefinition + 'static) -> Self {
+        Self(Arc::new(definition))
+    }
+
+    pub fn get_package_managers(&self) -> Vec<PackageManager> {
+        self.package_managers()
+    }
+
+    pub fn colorize(&self) -> ColoredString {
+        self.name().color(self.color()).bold()
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        self.id() == value || self.aliases().contains(&value)
+    }
+}
+
+impl Deref for Tech {
+    type Target = dyn TechDefinition;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for Tech {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Tech").field(&self.id()).finish()
+    }
+}
+
+impl fmt::Display for Tech {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl PartialEq for Tech {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl FromStr for Tech {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match registry().find(value) {
+            Some(tech) => Ok(tech.clone()),
+            None => bail!("Unknown tech: {value}"),
+        }
+    }
+}
+
+impl ValueParserFactory for Tech {
+    type Parser = clap::builder::MapValueParser<PossibleValuesParser, fn(String) -> Tech>;
+
+    fn value_parser() -> Self::Parser {
+        let values = registry()
+            .iter()
+            .map(|tech| PossibleValue::new(tech.id()).aliases(tech.aliases()));
+
+        PossibleValuesParser::new(values).map(|value| {
+            registry()
+                .find(&value)
+                .cloned()
+                .expect("value was validated against the registry")
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TechRegistry {
+    techs: Vec<Tech>,
+}
+
+static REGISTRY: OnceLock<TechRegistry> = OnceLock::new();
+
+/// Registry used by seedrs, initialized with the built-in and user techs on
+/// first use unless another one was [installed](TechRegistry::install) before.
+pub fn registry() -> &'static TechRegistry {
+    REGISTRY.get_or_init(TechRegistry::load)
+}
+
+impl TechRegistry {
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        for tech in builtin::techs() {
+            registry
+                .register(tech)
+                .expect("built-in techs have unique identifiers");
+        }
+        registry
+    }
+
+    /// Built-in techs plus the ones found in [`techs_dir`]. Invalid user techs
+    /// are reported and skipped so they can't prevent seedrs from running.
+    pub fn load() -> Self {
+        let mut registry = Self::with_builtins();
+        if let Some(dir) = techs_dir() {
+            if let Err(err) = registry.register_dir(&dir) {
+                eprintln!("{err:#}");
+            }
+        }
+        registry
+    }
+
+    pub fn register_dir(&mut self, dir: &Path) -> Result<()> {
+        for path in config::tech_files(dir)? {
+            let result = config::load(&path).and_then(|tech| self.register(tech));
+            if let Err(err) = result {
+                eprintln!("Skipping user tech {}: {err:#}", path.display());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn register(&mut self, definition: impl TechDefinition + 'static) -> Result<()> {
+        let tech = Tech::new(definition);
+
+        let identifiers = std::iter::once(tech.id()).chain(tech.aliases());
+        for identifier in identifiers {
+            if let Some(existing) = self.find(identifier) {
+                bail!(
+                    "Can't register {}, {identifier} is already used by {}",
+                    tech.id(),
+                    existing.id()
+                );
+            }
+        }
+
+        self.techs.push(tech);
+        Ok(())
+    }
+
+    /// Makes this registry the one used by seedrs, must be called before any tech is parsed.
+    pub fn install(self) -> Result<()> {
+        if REGISTRY.set(self).is_err() {
+            bail!("The tech registry was already initialized");
+        }
+        Ok(())
+    }
+
+    pub fn find(&self, value: &str) -> Option<&Tech> {
+        self.techs.iter().find(|tech| tech.matches(value))
+    }
+
+    pub fn of_kind(&self, kind: ProjectKind) -> Vec<Tech> {
+        self.techs
+            .iter()
+            .filter(|tech| tech.kind() == kind)
+            .cloned()
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Tech> {
+        self.techs.iter()
+    }
+}
@@ -1,6 +1,8 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use colored::Colorize;
+use degit::Repository;
 use std::io::{self, BufRead, Write};
+use which::which;
 
 use crate::{PackageManager, ProjectKind, Tech};
 
@@ -10,8 +12,10 @@ pub fn render_welcome() {
     println!();
 }
 
+pub const DEFAULT_PROJECT_PREFIX: &str = "my-project";
+
 pub fn render_project_naming_prompt() -> Result<String> {
-    let default_project = String::from("my-project");
+    let default_project = String::from(DEFAULT_PROJECT_PREFIX);
 
     let mut stdout = io::stdout();
     stdout.flush()?;
@@ -38,57 +42,54 @@ pub fn render_project_naming_prompt() -> Result<String> {
 }
 
 pub fn render_project_definition_prompt() -> Result<(i32, i32, i32)> {
-    let mut apis = None;
-    let mut webs = None;
-    let mut apps = None;
-
-    let kinds = ProjectKind::values();
-
-    let stdin = io::stdin();
-    let mut stdout = io::stdout();
+    let mut apis = 0;
+    let mut webs = 0;
+    let mut apps = 0;
 
-    for kind in kinds {
-        let ref_check = match kind {
+    for kind in ProjectKind::values() {
+        let count = match kind {
             ProjectKind::Web => &mut webs,
             ProjectKind::Mobile => &mut apps,
             ProjectKind::Api => &mut apis,
         };
+        *count =
+            render_project_count_prompt(&format!("How many {kind}s will the project have?"), 0)?;
+    }
+    println!();
 
-        while ref_check.is_none() {
-            let mut answer = String::new();
-            print!("How many {kind}s will the project have? (value >= 0, defaults to 0): ");
-            stdout.flush()?;
+    Ok((apis, webs, apps))
+}
 
-            if stdin.lock().read_line(&mut answer).is_err() {
-                println!("\nInvalid input, please insert a valid number!");
-                continue;
-            }
+pub fn render_project_count_prompt(question: &str, default: i32) -> Result<i32> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
 
-            let trimmed = answer.trim();
-            let parsed = if trimmed.is_empty() {
-                0
-            } else {
-                trimmed.parse::<i32>().unwrap_or(-1)
-            };
-
-            if parsed >= 0 {
-                *ref_check = Some(parsed);
-            } else {
-                println!("\nInvalid input, please insert a valid number!");
-            }
+    loop {
+        let mut answer = String::new();
+        print!("{question} (value >= 0, defaults to {default}): ");
+        stdout.flush()?;
+
+        if stdin.lock().read_line(&mut answer).is_err() {
+            println!("\nInvalid input, please insert a valid number!");
+            continue;
         }
-    }
-    println!();
 
-    Ok((apis.unwrap(), webs.unwrap(), apps.unwrap()))
+        let trimmed = answer.trim();
+        let parsed = if trimmed.is_empty() {
+            default
+        } else {
+            trimmed.parse::<i32>().unwrap_or(-1)
+        };
+
+        if parsed >= 0 {
+            return Ok(parsed);
+        }
+        println!("\nInvalid input, please insert a valid number!");
+    }
 }
 
 pub fn render_naming_prompt(prefix: &str, kind: ProjectKind, order: i32) -> Result<String> {
-    let default_name = if order == 1 {
-        format!("{}-{}", prefix, kind.get_suffix())
-    } else {
-        format!("{}-{}-{}", prefix, kind.get_suffix(), order)
-    };
+    let default_name = kind.default_name(prefix, order);
     let order_text = if order == 1 {
         String::new()
     } else {
@@ -201,54 +202,120 @@ pub fn render_tech_selection_prompt(project_name: &str, kind: ProjectKind) -> Re
     Ok(tech.unwrap().clone())
 }
 
+/// Returns the selected package manager and whether it has to be enabled with corepack.
 pub fn render_package_manager_selection_prompt(
     project_name: &str,
     tech: &Tech,
-) -> Result<PackageManager> {
+) -> Result<(PackageManager, bool)> {
     println!("\nPlease, select which package manager you will use for {project_name}:");
 
+    let corepack = which("corepack").is_ok();
     let package_managers_vec = tech.get_package_managers();
+    let availability: Vec<bool> = package_managers_vec
+        .iter()
+        .map(|pkg_manager| pkg_manager.check_if_availabe().is_ok())
+        .collect();
+
     for (index, pkg_manager) in package_managers_vec.iter().enumerate() {
-        println!("{}. {}", index + 1, pkg_manager.colorize());
+        if availability[index] {
+            println!("{}. {}", index + 1, pkg_manager.colorize());
+        } else if corepack && pkg_manager.is_corepack_managed() {
+            println!(
+                "{}. {} {}",
+                index + 1,
+                pkg_manager.colorize(),
+                "(not installed, can be enabled with corepack)".dimmed()
+            );
+        } else {
+            println!(
+                "{}",
+                format!("{}. {} (not installed)", index + 1, pkg_manager).dimmed()
+            );
+        }
+    }
+
+    let selectable = package_managers_vec
+        .iter()
+        .enumerate()
+        .any(|(index, pkg_manager)| {
+            availability[index] || (corepack && pkg_manager.is_corepack_managed())
+        });
+    if !selectable {
+        bail!("None of the package managers supported by {tech} are installed");
     }
 
-    let mut answer = String::new();
     let stdin = io::stdin();
     let mut stdout = io::stdout();
-    let mut package_manager = None;
-    while package_manager.is_none() {
+    loop {
+        let mut answer = String::new();
         print!("Enter a number: ");
         stdout.flush()?;
-        if stdin.lock().read_line(&mut answer).is_err() {
-            println!("\nInvalid input, please select one of the provided package managers!");
-            continue;
+        match stdin.lock().read_line(&mut answer) {
+            Ok(0) => bail!("No package manager was selected for {project_name}"),
+            Ok(_) => {}
+            Err(_) => {
+                println!("\nInvalid input, please select one of the provided package managers!");
+                continue;
+            }
         }
 
-        let parsed = answer.trim().parse::<usize>();
-        if parsed.is_err() {
+        let Some(index) = answer
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|parsed| (1..=package_managers_vec.len()).contains(parsed))
+            .map(|parsed| parsed - 1)
+        else {
             println!("\nInvalid input, please select one of the provided package managers!");
             continue;
+        };
+
+        let package_manager = &package_managers_vec[index];
+        if availability[index] {
+            return Ok((package_manager.clone(), false));
         }
-        let parsed = parsed.unwrap();
-        if parsed < 1 || parsed > package_managers_vec.len() {
-            println!("\nInvalid input, please select one of the provided package_manager!");
+        if !corepack || !package_manager.is_corepack_managed() {
+            println!("\n{package_manager} isn't installed, please select another package manager!");
             continue;
         }
+        if render_corepack_prompt(package_manager)? {
+            return Ok((package_manager.clone(), true));
+        }
+    }
+}
+
+fn render_corepack_prompt(package_manager: &PackageManager) -> Result<bool> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let mut answer = String::new();
+
+    loop {
+        print!(
+            "{package_manager} isn't installed, enable it with corepack? [no/anything else for {}] ",
+            "yes".bold()
+        );
+        stdout.flush()?;
 
-        package_manager = package_managers_vec.get(parsed - 1);
+        if stdin.lock().read_line(&mut answer).is_err() {
+            println!("\nPlease, insert a valid UTF-8 string!");
+            continue;
+        }
+        break;
     }
-    Ok(package_manager.unwrap().clone())
+
+    let result = answer.trim() != "no";
+    Ok(result)
 }
 
 pub fn render_template_choice_prompt(project_name: &str) -> Result<Option<String>> {
     let mut template = None;
 
-    let mut answer = String::new();
     let stdin = io::stdin();
     let mut stdout = io::stdout();
     loop {
+        let mut answer = String::new();
         print!(
-            "Would you like to use a git template for {project_name}? [{}/valid git repo url] ",
+            "Would you like to use a git template for {project_name}? [{}/git repo url or owner/repo] ",
             "no".bold()
         );
         stdout.flush()?;
@@ -258,12 +325,14 @@ pub fn render_template_choice_prompt(project_name: &str) -> Result<Option<String
         }
 
         let trimmed = answer.trim();
-        if trimmed.is_empty() {
+        if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("no") {
             break;
         }
 
-        if !trimmed.starts_with("https") && !trimmed.starts_with("git@") {
-            println!("Invalid git url provided!");
+        if let Err(err) = Repository::from_url(trimmed) {
+            println!(
+                "{err}, use a git repo url or a shorthand like owner/repo or gitlab:owner/repo!"
+            );
             continue;
         }
 